@@ -4,35 +4,27 @@ impl<T: ?Sized> NotDrop for &T {}
 impl<T: ?Sized> NotDrop for &mut T {}
 
 pub struct AliasGuard<F, S> {
-    first: std::mem::ManuallyDrop<F>,
+    first: core::mem::ManuallyDrop<F>,
     second: S,
 }
 
 impl<F, S> AliasGuard<F, S> {
     /// When AliasGuard is dropped F is not and S has their destructor ran normally!
     pub fn new(first: F, to_second: impl FnOnce(F) -> S) -> Self {
-        let second = unsafe {
-            let mut duplicate = std::mem::uninitialized();
-            std::ptr::copy_nonoverlapping(&first, &mut duplicate, 1);
-            to_second(duplicate)
-        };
+        let second = to_second(unsafe { core::ptr::read(&first) });
 
         AliasGuard {
-            first: std::mem::ManuallyDrop::new(first),
+            first: core::mem::ManuallyDrop::new(first),
             second,
         }
     }
 
     pub fn try_new<E>(first: F, to_second: impl FnOnce(F) -> Result<S, E>) -> Result<Self, (F, E)> {
-        let second = unsafe {
-            let mut duplicate = std::mem::uninitialized();
-            std::ptr::copy_nonoverlapping(&first, &mut duplicate, 1);
-            to_second(duplicate)
-        };
+        let second = to_second(unsafe { core::ptr::read(&first) });
 
         match second {
             Ok(second) => Ok(AliasGuard {
-                first: std::mem::ManuallyDrop::new(first),
+                first: core::mem::ManuallyDrop::new(first),
                 second,
             }),
             Err(e) => Err((first, e)),
@@ -59,7 +51,7 @@ impl<F, S> AliasGuard<F, S> {
     where
         F: NotDrop,
     {
-        std::mem::ManuallyDrop::into_inner(self.first)
+        core::mem::ManuallyDrop::into_inner(self.first)
     }
 
     pub fn move_second(self) -> S {
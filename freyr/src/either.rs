@@ -3,22 +3,27 @@ pub enum Either<L, R> {
     Right(R),
 }
 
+/// Aborts by panicking while unwinding, as `self` can't be dropped once `R` was moved out of it.
+struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        panic!("Either::unify_left panicked with its value moved out");
+    }
+}
+
 impl<L, R> Either<L, R> {
     pub fn unify_left(&mut self, func: impl FnOnce(R) -> L) -> &mut L {
-        let mut left = Either::Left(match self {
-            Either::Left(v) => return v,
-            Either::Right(right) => {
-                let mut right_uninit: R = unsafe { std::mem::uninitialized() };
-                std::mem::swap(right, &mut right_uninit);
-                func(right_uninit)
-            }
-        });
+        if let Either::Right(right) = self {
+            let right = unsafe { core::ptr::read(right) };
 
-        // Uninitialized value now inside self.
-        std::mem::swap(self, &mut left);
+            let guard = AbortOnUnwind;
+            let left = func(right);
+            core::mem::forget(guard);
 
-        // Uninitialized value now inside left.
-        std::mem::forget(left);
+            // The moved out `R` is overwritten without being dropped.
+            unsafe { core::ptr::write(self, Either::Left(left)) };
+        }
 
         match self {
             Either::Left(v) => v,
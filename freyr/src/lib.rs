@@ -1,3 +1,5 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 mod alias_guard;
 mod either;
 mod lazy;
@@ -46,13 +48,13 @@ pub mod prelude {
         fn take_exactly(self, n: usize) -> TakeExactly<Self::InnerIter>;
     }
 
-    impl<T> RepeatPrelude for std::iter::Repeat<T>
+    impl<T> RepeatPrelude for core::iter::Repeat<T>
     where
         T: Clone,
     {
-        type InnerIter = std::iter::Take<Self>;
+        type InnerIter = core::iter::Take<Self>;
 
-        fn take_exactly(self, n: usize) -> TakeExactly<std::iter::Take<Self>> {
+        fn take_exactly(self, n: usize) -> TakeExactly<core::iter::Take<Self>> {
             TakeExactly::new(self.take(n), n)
         }
     }
@@ -14,7 +14,7 @@ impl<T> ReadOnly<T> {
     }
 }
 
-impl<T> std::ops::Deref for ReadOnly<T> {
+impl<T> core::ops::Deref for ReadOnly<T> {
     type Target = T;
 
     fn deref(&self) -> &T {
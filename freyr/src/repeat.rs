@@ -10,7 +10,7 @@ impl<I> TakeExactly<I> {
     }
 }
 
-impl<I> std::ops::Deref for TakeExactly<I> {
+impl<I> core::ops::Deref for TakeExactly<I> {
     type Target = I;
 
     fn deref(&self) -> &I {
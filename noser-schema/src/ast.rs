@@ -0,0 +1,214 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Struct(Struct),
+    Enum(Enum),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Struct {
+    pub name: String,
+    pub fields: Fields,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+    pub name: String,
+    pub variants: Vec<Variant>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub name: String,
+    pub fields: Fields,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fields {
+    Unit,
+    Named(Vec<Field>),
+    Unnamed(Vec<Type>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Scalar(Scalar),
+    Bytes,
+    List(Box<Type>),
+    Named(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scalar {
+    Bool,
+    Char,
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    pub const ALL: [Scalar; 12] = [
+        Scalar::Bool,
+        Scalar::Char,
+        Scalar::U8,
+        Scalar::U16,
+        Scalar::U32,
+        Scalar::U64,
+        Scalar::I8,
+        Scalar::I16,
+        Scalar::I32,
+        Scalar::I64,
+        Scalar::F32,
+        Scalar::F64,
+    ];
+
+    /// The name of the scalar both in schemas and in rust.
+    pub fn name(self) -> &'static str {
+        match self {
+            Scalar::Bool => "bool",
+            Scalar::Char => "char",
+            Scalar::U8 => "u8",
+            Scalar::U16 => "u16",
+            Scalar::U32 => "u32",
+            Scalar::U64 => "u64",
+            Scalar::I8 => "i8",
+            Scalar::I16 => "i16",
+            Scalar::I32 => "i32",
+            Scalar::I64 => "i64",
+            Scalar::F32 => "f32",
+            Scalar::F64 => "f64",
+        }
+    }
+
+    pub fn size(self) -> usize {
+        match self {
+            Scalar::Bool | Scalar::U8 | Scalar::I8 => 1,
+            Scalar::U16 | Scalar::I16 => 2,
+            Scalar::Char | Scalar::U32 | Scalar::I32 | Scalar::F32 => 4,
+            Scalar::U64 | Scalar::I64 | Scalar::F64 => 8,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Scalar> {
+        Scalar::ALL.iter().cloned().find(|s| s.name() == name)
+    }
+}
+
+impl Item {
+    pub fn name(&self) -> &str {
+        match self {
+            Item::Struct(s) => &s.name,
+            Item::Enum(e) => &e.name,
+        }
+    }
+
+    /// Every field type of the item, for enums the fields of all variants.
+    pub fn field_types(&self) -> Vec<&Type> {
+        match self {
+            Item::Struct(s) => s.fields.types(),
+            Item::Enum(e) => e.variants.iter().flat_map(|v| v.fields.types()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Scalar(scalar) => f.write_str(scalar.name()),
+            Type::Bytes => f.write_str("bytes"),
+            Type::List(inner) => write!(f, "list<{}>", inner),
+            Type::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+impl Enum {
+    /// The size of the tag in front of the payload, the same as the derives use.
+    pub fn variant_bytes(&self) -> usize {
+        ((self.variants.len() as f64).log2() / 8.0).ceil().max(0.) as usize
+    }
+}
+
+impl Fields {
+    pub fn types(&self) -> Vec<&Type> {
+        match self {
+            Fields::Unit => vec![],
+            Fields::Named(fields) => fields.iter().map(|f| &f.ty).collect(),
+            Fields::Unnamed(types) => types.iter().collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.types().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Schema {
+    pub fn item(&self, name: &str) -> Option<&Item> {
+        self.items.iter().find(|item| item.name() == name)
+    }
+
+    /// Whether every instance of the type has the same size. The derives only support static
+    /// structs, enums are always dynamically sized.
+    pub fn is_static(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Scalar(_) => true,
+            Type::Bytes | Type::List(_) => false,
+            Type::Named(name) => match self.item(name) {
+                Some(Item::Struct(s)) => s.fields.types().iter().all(|ty| self.is_static(ty)),
+                _ => false,
+            },
+        }
+    }
+
+    /// The size of a static type.
+    pub fn static_size(&self, ty: &Type) -> Option<usize> {
+        if !self.is_static(ty) {
+            return None;
+        }
+
+        match ty {
+            Type::Scalar(scalar) => Some(scalar.size()),
+            Type::Named(name) => self
+                .item(name)
+                .map(|item| item.field_types())
+                .and_then(|types| types.iter().map(|ty| self.static_size(ty)).sum()),
+            _ => None,
+        }
+    }
+
+    /// Whether the generated rust type borrows the arena, types without any fields do not.
+    pub fn needs_lifetime(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Scalar(_) | Type::Bytes | Type::List(_) => true,
+            Type::Named(name) => self
+                .item(name)
+                .map(|item| item.field_types().iter().any(|ty| self.needs_lifetime(ty)))
+                .unwrap_or(false),
+        }
+    }
+}
@@ -0,0 +1,458 @@
+//! C headers and the matching `extern "C"` accessors, so C code can read noser buffers in place.
+//!
+//! Every accessor takes a view of a value and returns a view of one of its parts, or reads a
+//! scalar into an out parameter. A failed access, because the buffer is malformed or an enum
+//! holds another variant, returns a null view or `false`. The rust half is generated separately
+//! and expects the types of `rust::generate` to be in scope.
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::ast::*;
+use crate::rust::rust_type;
+use crate::Error;
+
+enum Output {
+    View,
+    Scalar(Scalar),
+    Tag(usize),
+}
+
+struct Accessor {
+    name: String,
+    input: String,
+    index: bool,
+    output: Output,
+    offset: Option<usize>,
+    /// Statements walking from the input to the accessed part, ending in the part's slice.
+    body: Vec<String>,
+    /// What the accessor reads, named in errors.
+    source: String,
+}
+
+struct Define {
+    name: String,
+    value: usize,
+    source: String,
+}
+
+struct Section {
+    comment: String,
+    defines: Vec<Define>,
+    accessors: Vec<Accessor>,
+}
+
+/// Generates the C header declaring the accessors, all symbols start with `prefix`. Fails if two
+/// parts of the schema would get the same symbol.
+pub fn header(schema: &Schema, prefix: &str) -> Result<String, Error> {
+    let upper = prefix.to_uppercase();
+    let mut out = String::new();
+
+    writeln!(out, "/* Generated by noser-schema, do not edit. */").unwrap();
+    writeln!(out, "#ifndef {}_NOSER_H", upper).unwrap();
+    writeln!(out, "#define {}_NOSER_H", upper).unwrap();
+    out.push_str("\n#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n");
+    out.push_str("\n#ifdef __cplusplus\nextern \"C\" {\n#endif\n");
+    writeln!(
+        out,
+        "\n/* A region of a noser buffer, `ptr` is NULL when an access failed. */"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "typedef struct {{\n    const uint8_t *ptr;\n    size_t len;\n}} {}_view;",
+        prefix
+    )
+    .unwrap();
+
+    for section in sections(schema)? {
+        writeln!(out, "\n/* {} */", section.comment).unwrap();
+
+        for define in &section.defines {
+            writeln!(
+                out,
+                "#define {}_{} {}",
+                upper,
+                define.name.to_uppercase(),
+                define.value
+            )
+            .unwrap();
+        }
+
+        for accessor in &section.accessors {
+            if let Some(offset) = accessor.offset {
+                writeln!(
+                    out,
+                    "#define {}_{}_OFFSET {}",
+                    upper,
+                    accessor.name.to_uppercase(),
+                    offset
+                )
+                .unwrap();
+            }
+        }
+
+        for accessor in &section.accessors {
+            let index = if accessor.index { ", uint32_t idx" } else { "" };
+            let (ret, out_param) = match accessor.output {
+                Output::View => (format!("{}_view", prefix), String::new()),
+                Output::Scalar(scalar) => {
+                    ("bool".to_string(), format!(", {} *out", c_type(scalar)))
+                }
+                Output::Tag(_) => ("bool".to_string(), ", uint64_t *out".to_string()),
+            };
+
+            writeln!(
+                out,
+                "{} {}_{}({}_view {}{}{});",
+                ret, prefix, accessor.name, prefix, accessor.input, index, out_param
+            )
+            .unwrap();
+        }
+    }
+
+    out.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif\n");
+    Ok(out)
+}
+
+/// Generates the rust implementation of the accessors declared by `header`.
+pub fn accessors(schema: &Schema, prefix: &str) -> Result<String, Error> {
+    let mut out = String::from("// Generated by noser-schema, do not edit.\n");
+
+    for accessor in sections(schema)?.into_iter().flat_map(|s| s.accessors) {
+        let index = if accessor.index { ", idx: u32" } else { "" };
+        let (out_param, ret) = match accessor.output {
+            Output::View => (String::new(), "::noser::ffi::View"),
+            Output::Scalar(scalar) => {
+                let out_param = format!(", out: *mut {}", scalar_rust_type(scalar));
+                (out_param, "bool")
+            }
+            Output::Tag(_) => (", out: *mut u64".to_string(), "bool"),
+        };
+
+        out.push_str("\n/// # Safety\n///\n/// The view must be null or readable");
+        if out_param.is_empty() {
+            out.push_str(".\n");
+        } else {
+            out.push_str(", `out` must be writable.\n");
+        }
+
+        writeln!(out, "#[no_mangle]").unwrap();
+        writeln!(
+            out,
+            "pub unsafe extern \"C\" fn {}_{}({}: ::noser::ffi::View{}{}) -> {} {{",
+            prefix, accessor.name, accessor.input, index, out_param, ret
+        )
+        .unwrap();
+
+        match accessor.output {
+            Output::Tag(variant_bytes) => {
+                writeln!(out, "    match {}", accessor.input).unwrap();
+                out.push_str("        .as_slice()\n");
+                writeln!(
+                    out,
+                    "        .and_then(|arena| ::noser::ffi::tag(arena, {}))",
+                    variant_bytes
+                )
+                .unwrap();
+                out.push_str("    {\n        Ok((tag, _)) => {\n            *out = tag;\n");
+                out.push_str("            true\n        }\n        Err(_) => false,\n    }\n");
+            }
+            _ => {
+                writeln!(
+                    out,
+                    "    let arena = {}.as_slice().and_then(|arena| {{",
+                    accessor.input
+                )
+                .unwrap();
+                for statement in &accessor.body {
+                    writeln!(out, "        {}", statement).unwrap();
+                }
+                out.push_str("    });\n");
+
+                match accessor.output {
+                    Output::Scalar(scalar) => writeln!(
+                        out,
+                        "    ::noser::ffi::read::<{}>(arena, out)",
+                        scalar_rust_type(scalar)
+                    )
+                    .unwrap(),
+                    _ => out.push_str("    ::noser::ffi::View::wrap(arena)\n"),
+                }
+            }
+        }
+
+        out.push_str("}\n");
+    }
+
+    Ok(out)
+}
+
+fn sections(schema: &Schema) -> Result<Vec<Section>, Error> {
+    let mut sections = vec![];
+
+    for item in &schema.items {
+        let name = snake_case(item.name());
+
+        sections.push(match item {
+            Item::Struct(s) => {
+                let ty = Type::Named(s.name.clone());
+                let defines = schema
+                    .static_size(&ty)
+                    .map(|size| {
+                        vec![Define {
+                            name: format!("{}_size", name),
+                            value: size,
+                            source: format!("the size of struct {}", s.name),
+                        }]
+                    })
+                    .unwrap_or_default();
+
+                Section {
+                    comment: format!("struct {}", s.name),
+                    defines,
+                    accessors: field_accessors(
+                        schema,
+                        &name,
+                        &s.name,
+                        &name,
+                        &s.fields,
+                        vec![],
+                        Some(0),
+                    ),
+                }
+            }
+            Item::Enum(e) => {
+                let variant_bytes = e.variant_bytes();
+                let mut defines = vec![Define {
+                    name: format!("{}_tag_bytes", name),
+                    value: variant_bytes,
+                    source: format!("the tag width of enum {}", e.name),
+                }];
+                let mut accessors = vec![Accessor {
+                    name: format!("{}_tag", name),
+                    input: name.clone(),
+                    index: false,
+                    output: Output::Tag(variant_bytes),
+                    offset: None,
+                    body: vec![],
+                    source: format!("the tag of enum {}", e.name),
+                }];
+
+                for (idx, variant) in e.variants.iter().enumerate() {
+                    let variant_name = format!("{}_{}", name, snake_case(&variant.name));
+                    let path = format!("{}::{}", e.name, variant.name);
+                    defines.push(Define {
+                        name: variant_name.clone(),
+                        value: idx,
+                        source: format!("variant {}", path),
+                    });
+
+                    accessors.extend(field_accessors(
+                        schema,
+                        &variant_name,
+                        &path,
+                        &name,
+                        &variant.fields,
+                        vec![format!(
+                            "let arena = ::noser::ffi::variant(arena, {}, {})?;",
+                            variant_bytes, idx
+                        )],
+                        Some(variant_bytes),
+                    ));
+                }
+
+                Section {
+                    comment: format!("enum {}", e.name),
+                    defines,
+                    accessors,
+                }
+            }
+        });
+    }
+
+    let mut accessors = vec![
+        Accessor {
+            name: "list_len".to_string(),
+            input: "list".to_string(),
+            index: false,
+            output: Output::Scalar(Scalar::U32),
+            offset: None,
+            body: vec!["::noser::ffi::field::<::noser::Literal<'static, u32>>(arena)".to_string()],
+            source: "the length of lists".to_string(),
+        },
+        Accessor {
+            name: "bytes".to_string(),
+            input: "bytes".to_string(),
+            index: false,
+            output: Output::View,
+            offset: None,
+            body: vec!["::noser::ffi::bytes(arena)".to_string()],
+            source: "the contents of bytes".to_string(),
+        },
+    ];
+
+    let mut elements = vec![];
+    for ty in schema.items.iter().flat_map(Item::field_types) {
+        list_elements(ty, &mut elements);
+    }
+
+    for element in elements {
+        accessors.push(Accessor {
+            name: format!("list_{}_get", mangle(element)),
+            input: "list".to_string(),
+            index: true,
+            output: Output::View,
+            offset: None,
+            body: vec![format!(
+                "::noser::ffi::list_get::<{}>(arena, idx)",
+                rust_type(schema, element, "'static")
+            )],
+            source: format!("the items of list<{}>", element),
+        });
+    }
+
+    sections.push(Section {
+        comment: "lists and bytes".to_string(),
+        defines: vec![],
+        accessors,
+    });
+
+    unique_symbols(&sections)?;
+    Ok(sections)
+}
+
+/// Fails if two parts of the schema get the same C symbol. Defines are upper case, so symbols
+/// are compared ignoring case.
+fn unique_symbols(sections: &[Section]) -> Result<(), Error> {
+    let mut symbols = vec![("view".to_string(), "the view type".to_string())];
+
+    for section in sections {
+        for define in &section.defines {
+            symbols.push((define.name.clone(), define.source.clone()));
+        }
+
+        for accessor in &section.accessors {
+            symbols.push((accessor.name.clone(), accessor.source.clone()));
+            if accessor.offset.is_some() {
+                symbols.push((
+                    format!("{}_offset", accessor.name),
+                    format!("the offset of {}", accessor.source),
+                ));
+            }
+        }
+    }
+
+    let mut seen = HashMap::new();
+    for (symbol, source) in &symbols {
+        if let Some(other) = seen.insert(symbol.to_lowercase(), source) {
+            return Err(Error::Invalid(format!(
+                "{} and {} both get the C symbol '{}'",
+                other, source, symbol
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// `path` is the struct or variant the fields belong to, e.g. `Action::Move`.
+fn field_accessors(
+    schema: &Schema,
+    name: &str,
+    path: &str,
+    input: &str,
+    fields: &Fields,
+    mut body: Vec<String>,
+    mut offset: Option<usize>,
+) -> Vec<Accessor> {
+    let names: Vec<String> = match fields {
+        Fields::Named(fields) => fields.iter().map(|f| f.name.clone()).collect(),
+        _ => (0..fields.len()).map(|i| i.to_string()).collect(),
+    };
+
+    let mut accessors = vec![];
+
+    for (field, ty) in names.iter().zip(fields.types()) {
+        let rust_ty = rust_type(schema, ty, "'static");
+
+        let mut field_body = body.clone();
+        field_body.push(format!("::noser::ffi::field::<{}>(arena)", rust_ty));
+
+        accessors.push(Accessor {
+            name: format!("{}_{}", name, field),
+            input: input.to_string(),
+            index: false,
+            output: match ty {
+                Type::Scalar(scalar) => Output::Scalar(*scalar),
+                _ => Output::View,
+            },
+            offset,
+            body: field_body,
+            source: format!("field {}.{}", path, field),
+        });
+
+        body.push(format!(
+            "let arena = ::noser::ffi::skip::<{}>(arena)?;",
+            rust_ty
+        ));
+        offset = offset.and_then(|offset| Some(offset + schema.static_size(ty)?));
+    }
+
+    accessors
+}
+
+fn list_elements<'s>(ty: &'s Type, elements: &mut Vec<&'s Type>) {
+    if let Type::List(inner) = ty {
+        if !elements.contains(&&**inner) {
+            elements.push(inner);
+        }
+        list_elements(inner, elements);
+    }
+}
+
+fn mangle(ty: &Type) -> String {
+    match ty {
+        Type::Scalar(scalar) => scalar.name().to_string(),
+        Type::Bytes => "bytes".to_string(),
+        Type::List(inner) => format!("list_{}", mangle(inner)),
+        Type::Named(name) => snake_case(name),
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+
+    snake
+}
+
+fn c_type(scalar: Scalar) -> &'static str {
+    match scalar {
+        Scalar::Bool => "bool",
+        Scalar::Char => "uint32_t",
+        Scalar::U8 => "uint8_t",
+        Scalar::U16 => "uint16_t",
+        Scalar::U32 => "uint32_t",
+        Scalar::U64 => "uint64_t",
+        Scalar::I8 => "int8_t",
+        Scalar::I16 => "int16_t",
+        Scalar::I32 => "int32_t",
+        Scalar::I64 => "int64_t",
+        Scalar::F32 => "float",
+        Scalar::F64 => "double",
+    }
+}
+
+/// Chars are handed to C as their code point, without validation.
+fn scalar_rust_type(scalar: Scalar) -> &'static str {
+    match scalar {
+        Scalar::Char => "u32",
+        _ => scalar.name(),
+    }
+}
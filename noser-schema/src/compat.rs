@@ -0,0 +1,370 @@
+//! Checks whether buffers written with one version of a schema can be read with another.
+//!
+//! Noser buffers carry no field names or versions, so only the layout matters: renaming fields,
+//! variants or types is free, while changing a literal width or the order of enum variants
+//! silently changes how every later byte is read.
+//!
+//! Fields may only be appended to the end of the root type, where an old reader decoding with
+//! `Message::from_prefix` ignores them. Every other reader, `Message::from_bytes`, streams and
+//! datagrams included, rejects the trailing bytes, for those only a compatible report is safe.
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::ast::*;
+use crate::Error;
+
+/// Which readers can no longer decode the buffers of the other version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breaks {
+    /// Readers using the old schema fail on buffers written with the new one.
+    OldReaders,
+    /// Readers using the new schema fail on buffers written with the old one.
+    NewReaders,
+    Both,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incompatibility {
+    /// Where the change is, e.g. `Proto.actions[]::Move.0`.
+    pub path: String,
+    pub breaks: Breaks,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report {
+    pub incompatibilities: Vec<Incompatibility>,
+}
+
+impl Report {
+    /// Whether readers using the old schema can decode buffers written with the new one, when
+    /// they decode with `Message::from_prefix`.
+    pub fn old_reads_new(&self) -> bool {
+        self.incompatibilities
+            .iter()
+            .all(|i| i.breaks == Breaks::NewReaders)
+    }
+
+    /// Whether readers using the new schema can decode buffers written with the old one, when
+    /// they decode with `Message::from_prefix`.
+    pub fn new_reads_old(&self) -> bool {
+        self.incompatibilities
+            .iter()
+            .all(|i| i.breaks == Breaks::OldReaders)
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.incompatibilities.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in &self.incompatibilities {
+            let breaks = match i.breaks {
+                Breaks::OldReaders => "breaks old readers",
+                Breaks::NewReaders => "breaks new readers",
+                Breaks::Both => "breaks all readers",
+            };
+
+            writeln!(f, "{}: {} ({})", i.path, i.reason, breaks)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares the type `root` of two schema versions. `root` is the type of whole buffers, it
+/// must exist in both schemas.
+pub fn check(old: &Schema, new: &Schema, root: &str) -> Result<Report, Error> {
+    for schema in &[old, new] {
+        if schema.item(root).is_none() {
+            return Err(Error::Invalid(format!("unknown type '{}'", root)));
+        }
+    }
+
+    let mut checker = Checker {
+        old,
+        new,
+        visited: HashSet::new(),
+        report: Report::default(),
+    };
+
+    let root = Type::Named(root.to_string());
+    checker.types(&root, &root, root.to_string(), true);
+
+    Ok(checker.report)
+}
+
+/// Panics with a description of every incompatibility between the two schema sources, for
+/// use in tests guarding a protocol.
+///
+/// ```
+/// noser_schema::compat::assert_compatible(
+///     "enum Direction { North, South }",
+///     "enum Direction { Up, Down }",
+///     "Direction",
+/// );
+/// ```
+pub fn assert_compatible(old: &str, new: &str, root: &str) {
+    let parse = |src| crate::parse(src).unwrap_or_else(|e| panic!("invalid schema: {}", e));
+
+    let report = check(&parse(old), &parse(new), root).unwrap_or_else(|e| panic!("{}", e));
+    if !report.is_compatible() {
+        panic!("incompatible schemas:\n{}", report);
+    }
+}
+
+struct Checker<'s> {
+    old: &'s Schema,
+    new: &'s Schema,
+    /// Pairs of named types already compared and whether they ended the buffer, recursive types
+    /// are only checked once per position.
+    visited: HashSet<(&'s str, &'s str, bool)>,
+    report: Report,
+}
+
+impl<'s> Checker<'s> {
+    fn incompatible(&mut self, path: &str, breaks: Breaks, reason: String) {
+        self.report.incompatibilities.push(Incompatibility {
+            path: path.to_string(),
+            breaks,
+            reason,
+        });
+    }
+
+    /// `tail` is whether the value ends the buffer, only then may fields be appended.
+    fn types(&mut self, old: &'s Type, new: &'s Type, path: String, tail: bool) {
+        match (old, new) {
+            (Type::Scalar(a), Type::Scalar(b)) if a == b => (),
+            (Type::Bytes, Type::Bytes) => (),
+            (Type::List(a), Type::List(b)) => self.types(a, b, format!("{}[]", path), false),
+            (Type::Named(a), Type::Named(b)) => {
+                if !self.visited.insert((a, b, tail)) {
+                    return;
+                }
+
+                match (self.old.item(a), self.new.item(b)) {
+                    (Some(Item::Struct(a)), Some(Item::Struct(b))) => {
+                        self.fields(&a.fields, &b.fields, &path, tail)
+                    }
+                    (Some(Item::Enum(a)), Some(Item::Enum(b))) => self.enums(a, b, &path, tail),
+                    _ => self.incompatible(
+                        &path,
+                        Breaks::Both,
+                        format!(
+                            "changed from {} to {}",
+                            kind(self.old, a),
+                            kind(self.new, b)
+                        ),
+                    ),
+                }
+            }
+            _ => self.incompatible(
+                &path,
+                Breaks::Both,
+                format!("changed from {} to {}", old, new),
+            ),
+        }
+    }
+
+    fn fields(&mut self, old: &'s Fields, new: &'s Fields, path: &str, tail: bool) {
+        let names = field_names(new);
+        let (old_types, new_types) = (old.types(), new.types());
+        let shared = old_types.len().min(new_types.len());
+
+        for (i, (a, b)) in old_types.iter().zip(&new_types).enumerate() {
+            let last = i + 1 == old_types.len() && i + 1 == new_types.len();
+            self.types(a, b, format!("{}.{}", path, names[i]), tail && last);
+        }
+
+        let breaks = |only| if tail { only } else { Breaks::Both };
+
+        for name in &names[shared..] {
+            self.incompatible(
+                path,
+                breaks(Breaks::NewReaders),
+                format!("added field {}", name),
+            );
+        }
+
+        for name in &field_names(old)[shared..] {
+            self.incompatible(
+                path,
+                breaks(Breaks::OldReaders),
+                format!("removed field {}", name),
+            );
+        }
+    }
+
+    fn enums(&mut self, old: &'s Enum, new: &'s Enum, path: &str, tail: bool) {
+        if old.variant_bytes() != new.variant_bytes() {
+            self.incompatible(
+                path,
+                Breaks::Both,
+                format!(
+                    "tags changed from {} to {} bytes",
+                    old.variant_bytes(),
+                    new.variant_bytes()
+                ),
+            );
+            return;
+        }
+
+        for (tag, (a, b)) in old.variants.iter().zip(&new.variants).enumerate() {
+            let moved = new.variants.iter().position(|v| v.name == a.name);
+
+            match moved {
+                Some(new_tag) if a.name != b.name => self.incompatible(
+                    path,
+                    Breaks::Both,
+                    format!("variant {} moved from tag {} to {}", a.name, tag, new_tag),
+                ),
+                _ => self.fields(&a.fields, &b.fields, &format!("{}::{}", path, a.name), tail),
+            }
+        }
+
+        for variant in new.variants.iter().skip(old.variants.len()) {
+            self.incompatible(
+                path,
+                Breaks::OldReaders,
+                format!("added variant {}", variant.name),
+            );
+        }
+
+        for variant in old.variants.iter().skip(new.variants.len()) {
+            self.incompatible(
+                path,
+                Breaks::NewReaders,
+                format!("removed variant {}", variant.name),
+            );
+        }
+    }
+}
+
+fn field_names(fields: &Fields) -> Vec<String> {
+    match fields {
+        Fields::Named(fields) => fields.iter().map(|f| f.name.clone()).collect(),
+        _ => (0..fields.len()).map(|i| i.to_string()).collect(),
+    }
+}
+
+fn kind(schema: &Schema, name: &str) -> String {
+    match schema.item(name) {
+        Some(Item::Struct(_)) => format!("struct {}", name),
+        Some(Item::Enum(_)) => format!("enum {}", name),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare(old: &str, new: &str) -> Report {
+        check(
+            &crate::parse(old).unwrap(),
+            &crate::parse(new).unwrap(),
+            "Root",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn renames_are_compatible() {
+        let report = compare(
+            "struct Root { a: u8, b: list<Item> } enum Item { X, Y(u16) }",
+            "struct Root { c: u8, d: list<Thing> } enum Thing { Z, W(u16) }",
+        );
+
+        assert!(report.is_compatible());
+    }
+
+    /// Appending to the root only spares readers using `Message::from_prefix`.
+    #[test]
+    fn appended_fields() {
+        let report = compare(
+            "struct Root { a: u8, b: Inner } struct Inner(u8);",
+            "struct Root { a: u8, b: Inner, c: u32 } struct Inner(u8, u8);",
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "Root.b: added field 1 (breaks all readers)\n\
+             Root: added field c (breaks new readers)\n"
+        );
+        assert!(!report.old_reads_new());
+        assert!(!report.new_reads_old());
+
+        let report = compare("struct Root(u8, u8);", "struct Root(u8);");
+        assert!(!report.old_reads_new());
+        assert!(report.new_reads_old());
+    }
+
+    #[test]
+    fn changed_widths() {
+        let report = compare(
+            "struct Root(list<u8>, bytes, Inner); struct Inner;",
+            "struct Root(list<u16>, list<u8>, Other); enum Other {}",
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "Root.0[]: changed from u8 to u16 (breaks all readers)\n\
+             Root.1: changed from bytes to list<u8> (breaks all readers)\n\
+             Root.2: changed from struct Inner to enum Other (breaks all readers)\n"
+        );
+    }
+
+    #[test]
+    fn enum_variants() {
+        let report = compare("enum Root { A, B, C(u8) }", "enum Root { A, C(u8), B, D }");
+
+        assert_eq!(
+            report.to_string(),
+            "Root: variant B moved from tag 1 to 2 (breaks all readers)\n\
+             Root: variant C moved from tag 2 to 1 (breaks all readers)\n\
+             Root: added variant D (breaks old readers)\n"
+        );
+
+        let report = compare("enum Root { A, B, C }", "enum Root { A, B }");
+        assert!(report.old_reads_new());
+        assert!(!report.new_reads_old());
+
+        let many = (0..257)
+            .map(|i| format!("V{}", i))
+            .collect::<Vec<_>>()
+            .join(",");
+        let report = compare("enum Root { V0 }", &format!("enum Root {{ {} }}", many));
+        assert_eq!(
+            report.to_string(),
+            "Root: tags changed from 0 to 2 bytes (breaks all readers)\n"
+        );
+    }
+
+    #[test]
+    fn recursive_types() {
+        let schema = "struct Root(list<Root>, u8);";
+        assert!(compare(schema, schema).is_compatible());
+    }
+
+    #[test]
+    fn types_in_and_out_of_tail_position() {
+        let report = compare(
+            "enum Root { A(P), B(P, u8) } struct P(u8);",
+            "enum Root { A(P), B(P, u8) } struct P(u8, u8);",
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "Root::A.0: added field 1 (breaks new readers)\n\
+             Root::B.0: added field 1 (breaks all readers)\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Root.0: changed from u8 to i8")]
+    fn assert_compatible_panics() {
+        assert_compatible("struct Root(u8);", "struct Root(i8);", "Root");
+    }
+}
@@ -0,0 +1,234 @@
+//! A small schema language for noser types and a code generator meant to be called from build
+//! scripts, so protocol definitions can live in one language neutral file.
+//!
+//! ```text
+//! // Comments start with two slashes.
+//! enum Direction { North, East, West, South }
+//!
+//! enum Action {
+//!     Move(Direction),
+//!     Aim { x: f32, y: f32 },
+//!     Jump,
+//! }
+//!
+//! struct Proto {
+//!     frame: u8,
+//!     actions: list<Action>,
+//!     name: bytes,
+//! }
+//! ```
+//!
+//! Scalars (`bool`, `char`, `u8`-`u64`, `i8`-`i64`, `f32`, `f64`) become `Literal`s, `list<T>`
+//! becomes a `List` and `bytes` a `&mut [u8]`. The `c` module generates headers and C-ABI
+//! accessors for the same types, `compat` checks whether two versions of a schema can read each
+//! other's buffers.
+#![deny(clippy::all)]
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+mod ast;
+pub mod c;
+pub mod compat;
+mod parser;
+pub mod rust;
+
+pub use crate::ast::*;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    Invalid(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "{}:{}: {}", line, column, message),
+            Error::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Parses and validates a schema.
+pub fn parse(src: &str) -> Result<Schema, Error> {
+    let schema = parser::Parser::new(src)?.schema()?;
+    schema.validate()?;
+    Ok(schema)
+}
+
+/// Generates rust code for the schema at `schema` and writes it to `out`. Meant to be called
+/// from a build script, the including crate needs `noser` and `noserc` as dependencies.
+///
+/// ```no_run
+/// let out_dir = std::env::var("OUT_DIR").unwrap();
+/// noser_schema::compile("protocol.noser", format!("{}/protocol.rs", out_dir)).unwrap();
+/// ```
+pub fn compile(schema: impl AsRef<Path>, out: impl AsRef<Path>) -> Result<(), Error> {
+    println!("cargo:rerun-if-changed={}", schema.as_ref().display());
+
+    let schema = parse(&std::fs::read_to_string(schema)?)?;
+    std::fs::write(out, rust::generate(&schema))?;
+    Ok(())
+}
+
+/// Like `compile`, but also writes a C header and the rust accessors it declares. All C symbols
+/// start with `prefix`, the accessors must be included next to the types written by `compile`.
+pub fn compile_c(
+    schema: impl AsRef<Path>,
+    prefix: &str,
+    header: impl AsRef<Path>,
+    accessors: impl AsRef<Path>,
+) -> Result<(), Error> {
+    println!("cargo:rerun-if-changed={}", schema.as_ref().display());
+
+    let schema = parse(&std::fs::read_to_string(schema)?)?;
+    std::fs::write(header, c::header(&schema, prefix)?)?;
+    std::fs::write(accessors, c::accessors(&schema, prefix)?)?;
+    Ok(())
+}
+
+impl Schema {
+    fn validate(&self) -> Result<(), Error> {
+        let mut names = HashSet::new();
+
+        for item in &self.items {
+            if !names.insert(item.name()) {
+                return Err(Error::Invalid(format!(
+                    "'{}' is defined more than once",
+                    item.name()
+                )));
+            }
+
+            match item {
+                Item::Struct(s) => unique(s.fields.names(), &s.name)?,
+                Item::Enum(e) => {
+                    unique(
+                        e.variants.iter().map(|v| v.name.as_str()).collect(),
+                        &e.name,
+                    )?;
+
+                    for variant in &e.variants {
+                        unique(
+                            variant.fields.names(),
+                            &format!("{}::{}", e.name, variant.name),
+                        )?;
+                    }
+                }
+            }
+
+            for ty in item.field_types() {
+                self.resolve(ty)?;
+            }
+        }
+
+        for item in &self.items {
+            self.check_recursion(item.name(), &mut vec![])?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve(&self, ty: &Type) -> Result<(), Error> {
+        match ty {
+            Type::List(inner) => self.resolve(inner),
+            Type::Named(name) if self.item(name).is_none() => {
+                Err(Error::Invalid(format!("unknown type '{}'", name)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// A type may only contain itself through a list, otherwise it would be infinitely large.
+    fn check_recursion<'s>(&'s self, name: &'s str, stack: &mut Vec<&'s str>) -> Result<(), Error> {
+        if stack.contains(&name) {
+            stack.push(name);
+            return Err(Error::Invalid(format!(
+                "recursive type without a list: {}",
+                stack.join(" -> ")
+            )));
+        }
+
+        stack.push(name);
+        for ty in self.item(name).map(Item::field_types).unwrap_or_default() {
+            if let Type::Named(inner) = ty {
+                self.check_recursion(inner, stack)?;
+            }
+        }
+        stack.pop();
+
+        Ok(())
+    }
+}
+
+impl Fields {
+    fn names(&self) -> Vec<&str> {
+        match self {
+            Fields::Named(fields) => fields.iter().map(|f| f.name.as_str()).collect(),
+            _ => vec![],
+        }
+    }
+}
+
+fn unique(names: Vec<&str>, scope: &str) -> Result<(), Error> {
+    let mut seen = HashSet::new();
+
+    match names.into_iter().find(|name| !seen.insert(*name)) {
+        Some(duplicate) => Err(Error::Invalid(format!(
+            "'{}' is used more than once in '{}'",
+            duplicate, scope
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_schemas() {
+        let cases = [
+            ("struct A; enum A {}", "'A' is defined more than once"),
+            (
+                "struct A { x: u8, x: u8 }",
+                "'x' is used more than once in 'A'",
+            ),
+            ("enum A { X, X }", "'X' is used more than once in 'A'"),
+            (
+                "enum A { X, Y { z: u8, z: u8 } }",
+                "'z' is used more than once in 'A::Y'",
+            ),
+            ("struct A { b: B }", "unknown type 'B'"),
+            (
+                "struct A(B); enum B { C(A) }",
+                "recursive type without a list: A -> B -> A",
+            ),
+            ("struct A(list<A>);", ""),
+        ];
+
+        for (src, expected) in cases.iter() {
+            let result = parse(src).err().map(|e| e.to_string()).unwrap_or_default();
+            assert_eq!(&result, expected);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::ast::*;
+use crate::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Punct(char),
+}
+
+struct Lexer<'s> {
+    chars: std::iter::Peekable<std::str::CharIndices<'s>>,
+    src: &'s str,
+}
+
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn of(src: &str, offset: usize) -> Position {
+        let before = &src[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+
+        Position { line, column }
+    }
+}
+
+impl<'s> Lexer<'s> {
+    fn tokens(src: &'s str) -> Result<Vec<(Token, usize)>, Error> {
+        let mut lexer = Lexer {
+            chars: src.char_indices().peekable(),
+            src,
+        };
+
+        let mut tokens = vec![];
+        while let Some(token) = lexer.next()? {
+            tokens.push(token);
+        }
+
+        Ok(tokens)
+    }
+
+    fn next(&mut self) -> Result<Option<(Token, usize)>, Error> {
+        loop {
+            let (start, c) = match self.chars.next() {
+                Some(next) => next,
+                None => return Ok(None),
+            };
+
+            if c.is_whitespace() {
+                continue;
+            }
+
+            if c == '/' && self.chars.peek().map(|(_, c)| *c) == Some('/') {
+                for (_, c) in self.chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                continue;
+            }
+
+            if c.is_ascii_alphabetic() || c == '_' {
+                let end = self.take_while(start, |c| c.is_ascii_alphanumeric() || c == '_');
+                return Ok(Some((
+                    Token::Ident(self.src[start..end].to_string()),
+                    start,
+                )));
+            }
+
+            return match c {
+                '{' | '}' | '(' | ')' | '<' | '>' | ',' | ':' | ';' => {
+                    Ok(Some((Token::Punct(c), start)))
+                }
+                _ => Err(Error::parse(self.src, start, "unexpected character")),
+            };
+        }
+    }
+
+    fn take_while(&mut self, start: usize, pred: impl Fn(char) -> bool) -> usize {
+        let mut end = start + 1;
+
+        while let Some((i, c)) = self.chars.peek().cloned() {
+            if !pred(c) {
+                break;
+            }
+            end = i + c.len_utf8();
+            self.chars.next();
+        }
+
+        end
+    }
+}
+
+pub(crate) struct Parser<'s> {
+    src: &'s str,
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    pub(crate) fn new(src: &'s str) -> Result<Self, Error> {
+        Ok(Parser {
+            src,
+            tokens: Lexer::tokens(src)?,
+            pos: 0,
+        })
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, Error> {
+        let offset = self
+            .tokens
+            .get(self.pos)
+            .map(|(_, offset)| *offset)
+            .unwrap_or_else(|| self.src.len());
+
+        Err(Error::parse(self.src, offset, message))
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        if self.peek() == Some(&Token::Punct(punct)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: char) -> Result<(), Error> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            self.error(&format!("expected '{}'", punct))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Some(Token::Ident(ident)) => {
+                let ident = ident.clone();
+                self.pos += 1;
+                Ok(ident)
+            }
+            _ => self.error("expected an identifier"),
+        }
+    }
+
+    pub(crate) fn schema(&mut self) -> Result<Schema, Error> {
+        let mut items = vec![];
+
+        while self.peek().is_some() {
+            items.push(self.item()?);
+        }
+
+        Ok(Schema { items })
+    }
+
+    fn item(&mut self) -> Result<Item, Error> {
+        match self.ident()?.as_str() {
+            "struct" => {
+                let name = self.ident()?;
+                let fields = self.fields()?;
+                self.eat(';');
+
+                Ok(Item::Struct(Struct { name, fields }))
+            }
+            "enum" => {
+                let name = self.ident()?;
+                self.expect('{')?;
+
+                let variants = self.separated('}', |parser| {
+                    let name = parser.ident()?;
+                    let fields = parser.fields()?;
+
+                    Ok(Variant { name, fields })
+                })?;
+
+                Ok(Item::Enum(Enum { name, variants }))
+            }
+            _ => {
+                self.pos -= 1;
+                self.error("expected 'struct' or 'enum'")
+            }
+        }
+    }
+
+    fn fields(&mut self) -> Result<Fields, Error> {
+        if self.eat('{') {
+            Ok(Fields::Named(self.separated('}', |parser| {
+                let name = parser.ident()?;
+                parser.expect(':')?;
+
+                Ok(Field {
+                    name,
+                    ty: parser.ty()?,
+                })
+            })?))
+        } else if self.eat('(') {
+            Ok(Fields::Unnamed(self.separated(')', Parser::ty)?))
+        } else {
+            Ok(Fields::Unit)
+        }
+    }
+
+    /// Comma separated elements up to and including the closing punctuation, a trailing comma is
+    /// allowed.
+    fn separated<T>(
+        &mut self,
+        close: char,
+        mut element: impl FnMut(&mut Self) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        let mut elements = vec![];
+
+        while !self.eat(close) {
+            elements.push(element(self)?);
+
+            if !self.eat(',') {
+                self.expect(close)?;
+                break;
+            }
+        }
+
+        Ok(elements)
+    }
+
+    fn ty(&mut self) -> Result<Type, Error> {
+        let name = self.ident()?;
+
+        Ok(match name.as_str() {
+            "list" => {
+                self.expect('<')?;
+                let inner = self.ty()?;
+                self.expect('>')?;
+
+                Type::List(Box::new(inner))
+            }
+            "bytes" => Type::Bytes,
+            _ => match Scalar::from_name(&name) {
+                Some(scalar) => Type::Scalar(scalar),
+                None => Type::Named(name),
+            },
+        })
+    }
+}
+
+impl Error {
+    fn parse(src: &str, offset: usize, message: &str) -> Error {
+        let Position { line, column } = Position::of(src, offset);
+
+        Error::Parse {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_items() {
+        let schema = crate::parse(
+            "// A comment
+            struct Point(i32, i32);
+            struct Unit;
+            enum Shape { Dot(Point), Line { from: Point, to: Point, }, Empty }
+            struct Drawing { shapes: list<Shape>, name: bytes }",
+        )
+        .unwrap();
+
+        assert_eq!(schema.items.len(), 4);
+        assert_eq!(
+            schema.item("Drawing"),
+            Some(&Item::Struct(Struct {
+                name: "Drawing".to_string(),
+                fields: Fields::Named(vec![
+                    Field {
+                        name: "shapes".to_string(),
+                        ty: Type::List(Box::new(Type::Named("Shape".to_string()))),
+                    },
+                    Field {
+                        name: "name".to_string(),
+                        ty: Type::Bytes,
+                    },
+                ]),
+            }))
+        );
+    }
+
+    #[test]
+    fn error_position() {
+        assert_eq!(
+            crate::parse("struct A {\n  x u8\n}")
+                .unwrap_err()
+                .to_string(),
+            "2:5: expected ':'"
+        );
+
+        assert_eq!(
+            crate::parse("union A {}").unwrap_err().to_string(),
+            "1:1: expected 'struct' or 'enum'"
+        );
+    }
+}
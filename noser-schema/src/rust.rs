@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use crate::ast::*;
+
+/// Generates the rust types of a schema. The generated code expects the `noser` and `noserc`
+/// crates to be available to the crate including it.
+pub fn generate(schema: &Schema) -> String {
+    let mut out = String::from("// Generated by noser-schema, do not edit.\n");
+
+    for item in &schema.items {
+        out.push('\n');
+        item_code(schema, item, &mut out);
+    }
+
+    out
+}
+
+/// The rust type of a field, borrowing the arena for `lifetime`.
+pub(crate) fn rust_type(schema: &Schema, ty: &Type, lifetime: &str) -> String {
+    match ty {
+        Type::Scalar(scalar) => format!("::noser::Literal<{}, {}>", lifetime, scalar.name()),
+        Type::Bytes => format!("&{} mut [u8]", lifetime),
+        Type::List(inner) => format!(
+            "::noser::List<{}, {}>",
+            lifetime,
+            rust_type(schema, inner, lifetime)
+        ),
+        Type::Named(name) if schema.needs_lifetime(ty) => format!("{}<{}>", name, lifetime),
+        Type::Named(name) => name.clone(),
+    }
+}
+
+fn derives(schema: &Schema, item: &Item) -> Vec<&'static str> {
+    let ty = Type::Named(item.name().to_string());
+    let size = if schema.is_static(&ty) {
+        "StaticSizeable"
+    } else {
+        "DynamicSizeable"
+    };
+
+    vec![
+        "Build",
+        size,
+        "WriteTypeInfo",
+        "Json",
+        "CopyInto",
+        "Structural",
+        "Diff",
+    ]
+}
+
+fn fields_code(schema: &Schema, fields: &Fields, vis: &str, indent: &str, out: &mut String) {
+    match fields {
+        Fields::Unit => (),
+        Fields::Named(fields) => {
+            out.push_str(" {\n");
+            for field in fields {
+                writeln!(
+                    out,
+                    "{}    {}{}: {},",
+                    indent,
+                    vis,
+                    field.name,
+                    rust_type(schema, &field.ty, "'a")
+                )
+                .unwrap();
+            }
+            write!(out, "{}}}", indent).unwrap();
+        }
+        Fields::Unnamed(types) => {
+            let types = types
+                .iter()
+                .map(|ty| format!("{}{}", vis, rust_type(schema, ty, "'a")))
+                .collect::<Vec<_>>();
+            write!(out, "({})", types.join(", ")).unwrap();
+        }
+    }
+}
+
+fn item_code(schema: &Schema, item: &Item, out: &mut String) {
+    let derives = derives(schema, item)
+        .iter()
+        .map(|derive| format!("::noserc::{}", derive))
+        .collect::<Vec<_>>();
+
+    writeln!(out, "#[derive({})]", derives.join(", ")).unwrap();
+
+    let lifetime = if schema.needs_lifetime(&Type::Named(item.name().to_string())) {
+        "<'a>"
+    } else {
+        ""
+    };
+
+    match item {
+        Item::Struct(s) => {
+            write!(out, "pub struct {}{}", s.name, lifetime).unwrap();
+            fields_code(schema, &s.fields, "pub ", "", out);
+
+            match s.fields {
+                Fields::Named(_) => out.push('\n'),
+                _ => out.push_str(";\n"),
+            }
+        }
+        Item::Enum(e) => {
+            writeln!(out, "pub enum {}{} {{", e.name, lifetime).unwrap();
+            for variant in &e.variants {
+                write!(out, "    {}", variant.name).unwrap();
+                fields_code(schema, &variant.fields, "", "    ", out);
+                out.push_str(",\n");
+            }
+            out.push_str("}\n");
+        }
+    }
+}
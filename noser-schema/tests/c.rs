@@ -0,0 +1,123 @@
+mod protocol {
+    #![allow(dead_code)]
+    include!("fixtures/protocol.rs");
+    include!("fixtures/protocol_c.rs");
+}
+
+use noser::ffi::View;
+use noser::json::{from_json, Value};
+use protocol::*;
+
+const PROTO: &str = concat!(
+    r#"{"frame":3,"position":{"x":-4,"y":9},"actions":["#,
+    r#"{"Move":"West"},{"Aim":{"x":0.5,"y":-1.0}},{"Say":[104,105]},"Jump"]}"#
+);
+
+fn proto_arena() -> Vec<u8> {
+    from_json::<Proto>(&Value::parse(PROTO).unwrap()).unwrap()
+}
+
+#[test]
+fn matches_fixtures() {
+    let schema = noser_schema::parse(include_str!("fixtures/protocol.noser")).unwrap();
+
+    assert_eq!(
+        noser_schema::c::header(&schema, "proto").unwrap(),
+        include_str!("fixtures/protocol.h")
+    );
+    assert_eq!(
+        noser_schema::c::accessors(&schema, "proto").unwrap(),
+        include_str!("fixtures/protocol_c.rs")
+    );
+}
+
+#[test]
+fn read_through_accessors() {
+    let arena = proto_arena();
+    let proto = View::new(&arena);
+
+    unsafe {
+        let mut frame = 0;
+        assert!(proto_proto_frame(proto, &mut frame));
+        assert_eq!(frame, 3);
+
+        let mut y = 0;
+        assert!(proto_position_y(proto_proto_position(proto), &mut y));
+        assert_eq!(y, 9);
+
+        let actions = proto_proto_actions(proto);
+        let mut len = 0;
+        assert!(proto_list_len(actions, &mut len));
+        assert_eq!(len, 4);
+
+        let mut tag = 0;
+        let direction = proto_action_move_0(proto_list_action_get(actions, 0));
+        assert!(proto_direction_tag(direction, &mut tag));
+        assert_eq!(tag, 2);
+
+        let aim = proto_list_action_get(actions, 1);
+        let mut x = 0.0;
+        assert!(proto_action_tag(aim, &mut tag));
+        assert_eq!(tag, 2);
+        assert!(proto_action_aim_x(aim, &mut x));
+        assert_eq!(x, 0.5);
+
+        let say = proto_action_say_0(proto_list_action_get(actions, 2));
+        let text = proto_bytes(say).as_slice().unwrap();
+        assert_eq!(text, b"hi");
+    }
+}
+
+#[test]
+fn failed_accesses() {
+    let arena = proto_arena();
+    let actions = unsafe { proto_proto_actions(View::new(&arena)) };
+
+    unsafe {
+        let mut x = 0.0;
+        let aim = proto_list_action_get(actions, 1);
+        assert!(proto_action_move_0(aim).ptr.is_null());
+        assert!(!proto_action_aim_x(
+            proto_list_action_get(actions, 0),
+            &mut x
+        ));
+        assert!(proto_list_action_get(actions, 4).ptr.is_null());
+        assert!(proto_proto_actions(View::NULL).ptr.is_null());
+    }
+
+    for len in 0..arena.len() {
+        let proto = View::new(&arena[..len]);
+        let mut frame = 0;
+
+        unsafe {
+            assert_eq!(proto_proto_frame(proto, &mut frame), len > 0);
+            assert!(proto_list_action_get(proto_proto_actions(proto), 3)
+                .ptr
+                .is_null());
+        }
+    }
+}
+
+#[test]
+fn symbol_collisions() {
+    let error = |src: &str| {
+        let schema = noser_schema::parse(src).unwrap();
+        let header = noser_schema::c::header(&schema, "proto").unwrap_err();
+        let accessors = noser_schema::c::accessors(&schema, "proto").unwrap_err();
+        assert_eq!(header.to_string(), accessors.to_string());
+        header.to_string()
+    };
+
+    assert_eq!(
+        error("struct FooBar { baz: u8 } struct Foo { bar_baz: u8 }"),
+        "field FooBar.baz and field Foo.bar_baz both get the C symbol 'foo_bar_baz'"
+    );
+    assert_eq!(
+        error("struct Foo { size: u8 }"),
+        "the size of struct Foo and field Foo.size both get the C symbol 'foo_size'"
+    );
+    assert_eq!(
+        error("enum Foo { Tag, Other }"),
+        "variant Foo::Tag and the tag of enum Foo both get the C symbol 'foo_tag'"
+    );
+}
@@ -0,0 +1,47 @@
+use noser_schema::compat::{assert_compatible, check, Breaks};
+
+const PROTOCOL: &str = include_str!("fixtures/protocol.noser");
+
+#[test]
+fn protocol_is_compatible_with_itself() {
+    assert_compatible(PROTOCOL, PROTOCOL, "Proto");
+}
+
+#[test]
+fn reordered_actions_are_caught() {
+    let reordered = PROTOCOL.replace(
+        "    Move(Direction),\n    Shoot(Direction),",
+        "    Shoot(Direction),\n    Move(Direction),",
+    );
+    assert_ne!(reordered, PROTOCOL);
+
+    let report = check(
+        &noser_schema::parse(PROTOCOL).unwrap(),
+        &noser_schema::parse(&reordered).unwrap(),
+        "Proto",
+    )
+    .unwrap();
+
+    assert_eq!(report.incompatibilities.len(), 2);
+    assert_eq!(report.incompatibilities[0].path, "Proto.actions[]");
+    assert_eq!(report.incompatibilities[0].breaks, Breaks::Both);
+    assert!(!report.old_reads_new() && !report.new_reads_old());
+}
+
+#[test]
+fn appending_to_the_root_only_breaks_new_readers() {
+    let extended = PROTOCOL.replace(
+        "actions: list<Action>,",
+        "actions: list<Action>,\n    seq: u32,",
+    );
+
+    let report = check(
+        &noser_schema::parse(PROTOCOL).unwrap(),
+        &noser_schema::parse(&extended).unwrap(),
+        "Proto",
+    )
+    .unwrap();
+
+    assert!(report.old_reads_new());
+    assert!(!report.new_reads_old());
+}
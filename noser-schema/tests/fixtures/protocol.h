@@ -0,0 +1,70 @@
+/* Generated by noser-schema, do not edit. */
+#ifndef PROTO_NOSER_H
+#define PROTO_NOSER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A region of a noser buffer, `ptr` is NULL when an access failed. */
+typedef struct {
+    const uint8_t *ptr;
+    size_t len;
+} proto_view;
+
+/* enum Direction */
+#define PROTO_DIRECTION_TAG_BYTES 1
+#define PROTO_DIRECTION_NORTH 0
+#define PROTO_DIRECTION_EAST 1
+#define PROTO_DIRECTION_WEST 2
+#define PROTO_DIRECTION_SOUTH 3
+bool proto_direction_tag(proto_view direction, uint64_t *out);
+
+/* enum Action */
+#define PROTO_ACTION_TAG_BYTES 1
+#define PROTO_ACTION_MOVE 0
+#define PROTO_ACTION_SHOOT 1
+#define PROTO_ACTION_AIM 2
+#define PROTO_ACTION_SAY 3
+#define PROTO_ACTION_JUMP 4
+#define PROTO_ACTION_MOVE_0_OFFSET 1
+#define PROTO_ACTION_SHOOT_0_OFFSET 1
+#define PROTO_ACTION_AIM_X_OFFSET 1
+#define PROTO_ACTION_AIM_Y_OFFSET 5
+#define PROTO_ACTION_SAY_0_OFFSET 1
+bool proto_action_tag(proto_view action, uint64_t *out);
+proto_view proto_action_move_0(proto_view action);
+proto_view proto_action_shoot_0(proto_view action);
+bool proto_action_aim_x(proto_view action, float *out);
+bool proto_action_aim_y(proto_view action, float *out);
+proto_view proto_action_say_0(proto_view action);
+
+/* struct Position */
+#define PROTO_POSITION_SIZE 8
+#define PROTO_POSITION_X_OFFSET 0
+#define PROTO_POSITION_Y_OFFSET 4
+bool proto_position_x(proto_view position, int32_t *out);
+bool proto_position_y(proto_view position, int32_t *out);
+
+/* struct Proto */
+#define PROTO_PROTO_FRAME_OFFSET 0
+#define PROTO_PROTO_POSITION_OFFSET 1
+#define PROTO_PROTO_ACTIONS_OFFSET 9
+bool proto_proto_frame(proto_view proto, uint8_t *out);
+proto_view proto_proto_position(proto_view proto);
+proto_view proto_proto_actions(proto_view proto);
+
+/* lists and bytes */
+bool proto_list_len(proto_view list, uint32_t *out);
+proto_view proto_bytes(proto_view bytes);
+proto_view proto_list_action_get(proto_view list, uint32_t idx);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,34 @@
+// Generated by noser-schema, do not edit.
+
+#[derive(::noserc::Build, ::noserc::DynamicSizeable, ::noserc::WriteTypeInfo, ::noserc::Json, ::noserc::CopyInto, ::noserc::Structural, ::noserc::Diff)]
+pub enum Direction {
+    North,
+    East,
+    West,
+    South,
+}
+
+#[derive(::noserc::Build, ::noserc::DynamicSizeable, ::noserc::WriteTypeInfo, ::noserc::Json, ::noserc::CopyInto, ::noserc::Structural, ::noserc::Diff)]
+pub enum Action<'a> {
+    Move(Direction),
+    Shoot(Direction),
+    Aim {
+        x: ::noser::Literal<'a, f32>,
+        y: ::noser::Literal<'a, f32>,
+    },
+    Say(&'a mut [u8]),
+    Jump,
+}
+
+#[derive(::noserc::Build, ::noserc::StaticSizeable, ::noserc::WriteTypeInfo, ::noserc::Json, ::noserc::CopyInto, ::noserc::Structural, ::noserc::Diff)]
+pub struct Position<'a> {
+    pub x: ::noser::Literal<'a, i32>,
+    pub y: ::noser::Literal<'a, i32>,
+}
+
+#[derive(::noserc::Build, ::noserc::DynamicSizeable, ::noserc::WriteTypeInfo, ::noserc::Json, ::noserc::CopyInto, ::noserc::Structural, ::noserc::Diff)]
+pub struct Proto<'a> {
+    pub frame: ::noser::Literal<'a, u8>,
+    pub position: Position<'a>,
+    pub actions: ::noser::List<'a, Action<'a>>,
+}
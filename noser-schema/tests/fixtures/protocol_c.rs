@@ -0,0 +1,188 @@
+// Generated by noser-schema, do not edit.
+
+/// # Safety
+///
+/// The view must be null or readable, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_direction_tag(direction: ::noser::ffi::View, out: *mut u64) -> bool {
+    match direction
+        .as_slice()
+        .and_then(|arena| ::noser::ffi::tag(arena, 1))
+    {
+        Ok((tag, _)) => {
+            *out = tag;
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// # Safety
+///
+/// The view must be null or readable, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_action_tag(action: ::noser::ffi::View, out: *mut u64) -> bool {
+    match action
+        .as_slice()
+        .and_then(|arena| ::noser::ffi::tag(arena, 1))
+    {
+        Ok((tag, _)) => {
+            *out = tag;
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// # Safety
+///
+/// The view must be null or readable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_action_move_0(action: ::noser::ffi::View) -> ::noser::ffi::View {
+    let arena = action.as_slice().and_then(|arena| {
+        let arena = ::noser::ffi::variant(arena, 1, 0)?;
+        ::noser::ffi::field::<Direction>(arena)
+    });
+    ::noser::ffi::View::wrap(arena)
+}
+
+/// # Safety
+///
+/// The view must be null or readable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_action_shoot_0(action: ::noser::ffi::View) -> ::noser::ffi::View {
+    let arena = action.as_slice().and_then(|arena| {
+        let arena = ::noser::ffi::variant(arena, 1, 1)?;
+        ::noser::ffi::field::<Direction>(arena)
+    });
+    ::noser::ffi::View::wrap(arena)
+}
+
+/// # Safety
+///
+/// The view must be null or readable, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_action_aim_x(action: ::noser::ffi::View, out: *mut f32) -> bool {
+    let arena = action.as_slice().and_then(|arena| {
+        let arena = ::noser::ffi::variant(arena, 1, 2)?;
+        ::noser::ffi::field::<::noser::Literal<'static, f32>>(arena)
+    });
+    ::noser::ffi::read::<f32>(arena, out)
+}
+
+/// # Safety
+///
+/// The view must be null or readable, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_action_aim_y(action: ::noser::ffi::View, out: *mut f32) -> bool {
+    let arena = action.as_slice().and_then(|arena| {
+        let arena = ::noser::ffi::variant(arena, 1, 2)?;
+        let arena = ::noser::ffi::skip::<::noser::Literal<'static, f32>>(arena)?;
+        ::noser::ffi::field::<::noser::Literal<'static, f32>>(arena)
+    });
+    ::noser::ffi::read::<f32>(arena, out)
+}
+
+/// # Safety
+///
+/// The view must be null or readable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_action_say_0(action: ::noser::ffi::View) -> ::noser::ffi::View {
+    let arena = action.as_slice().and_then(|arena| {
+        let arena = ::noser::ffi::variant(arena, 1, 3)?;
+        ::noser::ffi::field::<&'static mut [u8]>(arena)
+    });
+    ::noser::ffi::View::wrap(arena)
+}
+
+/// # Safety
+///
+/// The view must be null or readable, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_position_x(position: ::noser::ffi::View, out: *mut i32) -> bool {
+    let arena = position.as_slice().and_then(|arena| {
+        ::noser::ffi::field::<::noser::Literal<'static, i32>>(arena)
+    });
+    ::noser::ffi::read::<i32>(arena, out)
+}
+
+/// # Safety
+///
+/// The view must be null or readable, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_position_y(position: ::noser::ffi::View, out: *mut i32) -> bool {
+    let arena = position.as_slice().and_then(|arena| {
+        let arena = ::noser::ffi::skip::<::noser::Literal<'static, i32>>(arena)?;
+        ::noser::ffi::field::<::noser::Literal<'static, i32>>(arena)
+    });
+    ::noser::ffi::read::<i32>(arena, out)
+}
+
+/// # Safety
+///
+/// The view must be null or readable, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_proto_frame(proto: ::noser::ffi::View, out: *mut u8) -> bool {
+    let arena = proto.as_slice().and_then(|arena| {
+        ::noser::ffi::field::<::noser::Literal<'static, u8>>(arena)
+    });
+    ::noser::ffi::read::<u8>(arena, out)
+}
+
+/// # Safety
+///
+/// The view must be null or readable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_proto_position(proto: ::noser::ffi::View) -> ::noser::ffi::View {
+    let arena = proto.as_slice().and_then(|arena| {
+        let arena = ::noser::ffi::skip::<::noser::Literal<'static, u8>>(arena)?;
+        ::noser::ffi::field::<Position<'static>>(arena)
+    });
+    ::noser::ffi::View::wrap(arena)
+}
+
+/// # Safety
+///
+/// The view must be null or readable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_proto_actions(proto: ::noser::ffi::View) -> ::noser::ffi::View {
+    let arena = proto.as_slice().and_then(|arena| {
+        let arena = ::noser::ffi::skip::<::noser::Literal<'static, u8>>(arena)?;
+        let arena = ::noser::ffi::skip::<Position<'static>>(arena)?;
+        ::noser::ffi::field::<::noser::List<'static, Action<'static>>>(arena)
+    });
+    ::noser::ffi::View::wrap(arena)
+}
+
+/// # Safety
+///
+/// The view must be null or readable, `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_list_len(list: ::noser::ffi::View, out: *mut u32) -> bool {
+    let arena = list.as_slice().and_then(|arena| {
+        ::noser::ffi::field::<::noser::Literal<'static, u32>>(arena)
+    });
+    ::noser::ffi::read::<u32>(arena, out)
+}
+
+/// # Safety
+///
+/// The view must be null or readable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_bytes(bytes: ::noser::ffi::View) -> ::noser::ffi::View {
+    let arena = bytes.as_slice().and_then(|arena| {
+        ::noser::ffi::bytes(arena)
+    });
+    ::noser::ffi::View::wrap(arena)
+}
+
+/// # Safety
+///
+/// The view must be null or readable.
+#[no_mangle]
+pub unsafe extern "C" fn proto_list_action_get(list: ::noser::ffi::View, idx: u32) -> ::noser::ffi::View {
+    let arena = list.as_slice().and_then(|arena| {
+        ::noser::ffi::list_get::<Action<'static>>(arena, idx)
+    });
+    ::noser::ffi::View::wrap(arena)
+}
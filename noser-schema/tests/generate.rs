@@ -0,0 +1,209 @@
+mod protocol {
+    #![allow(dead_code)]
+    include!("fixtures/protocol.rs");
+}
+
+use noser::json::{from_json, to_json, Value};
+use noser::traits::{Build, DefaultWriter, WriteTypeInfo};
+use protocol::*;
+
+#[test]
+fn matches_fixture() {
+    let schema = noser_schema::parse(include_str!("fixtures/protocol.noser")).unwrap();
+
+    assert_eq!(
+        noser_schema::rust::generate(&schema),
+        include_str!("fixtures/protocol.rs")
+    );
+}
+
+#[test]
+fn generated_types() {
+    let json = r#"{"frame":3,"position":{"x":-4,"y":9},"actions":[{"Move":"West"},{"Say":[104,105]},"Jump"]}"#;
+
+    let mut arena = from_json::<Proto>(&Value::parse(json).unwrap()).unwrap();
+    let proto = Proto::create(&mut arena).unwrap();
+
+    assert_eq!(proto.frame.read(), 3);
+    assert_eq!(proto.position.x.read(), -4);
+    assert_eq!(proto.actions.capacity(), 3);
+    assert!(freyr::matches!(
+        *proto.actions.borrow(0),
+        Action::Move(Direction::West)
+    ));
+    match *proto.actions.borrow(1) {
+        Action::Say(ref text) => assert_eq!(&text[..], b"hi"),
+        _ => panic!("expected Action::Say"),
+    }
+
+    assert_eq!(to_json::<Proto>(&arena).unwrap().to_string(), json);
+}
+
+#[test]
+fn static_items_get_writers() {
+    let writer = Position::writer();
+    let mut arena = writer.create_buffer().unwrap();
+
+    assert_eq!(arena.len(), 8);
+    let mut position = Position::create(&mut arena).unwrap();
+    position.y.write(12);
+    assert_eq!(position.y.read(), 12);
+}
+
+#[test]
+fn writers_pick_list_capacities() {
+    use noser::writer::{list::WithCapacity, slice::SliceWriter};
+    use noser::{List, Literal};
+
+    let say = SliceWriter::with_capacity(2);
+    let actions: [&dyn WriteTypeInfo<Action>; 3] = [
+        &ActionWriter::Move(&DirectionWriter::North),
+        &ActionWriter::Say(&say),
+        &ActionWriter::Jump,
+    ];
+    let actions = List::from(&actions);
+    let writer = ProtoWriter {
+        frame: &Literal::<u8>::with_value(1),
+        position: Position::writer(),
+        actions: &actions,
+    };
+
+    let arena = writer.create_buffer().unwrap();
+    assert_eq!(
+        to_json::<Proto>(&arena).unwrap().to_string(),
+        r#"{"frame":1,"position":{"x":0,"y":0},"actions":[{"Move":"North"},{"Say":[0,0]},"Jump"]}"#
+    );
+
+    let jumps = WithCapacity::repeat(&ActionWriter::Jump, 5);
+    let writer = ProtoWriter {
+        frame: &Literal::<u8>::with_value(2),
+        position: Position::writer(),
+        actions: &jumps,
+    };
+
+    let mut arena = writer.create_buffer().unwrap();
+    let proto = Proto::create(&mut arena).unwrap();
+    assert_eq!(proto.actions.capacity(), 5);
+    assert!(freyr::matches!(*proto.actions.borrow(4), Action::Jump));
+}
+
+#[test]
+fn message_builder_matches_json() {
+    use noser::builder::MessageBuilder;
+
+    let json = r#"{"frame":3,"position":{"x":-4,"y":9},"actions":[{"Move":"West"},{"Say":[104,105]},"Jump"]}"#;
+
+    let mut builder = MessageBuilder::new();
+    builder
+        .literal(3u8)
+        .literal(-4i32)
+        .literal(9i32)
+        .list(|actions| {
+            actions.item(|b| {
+                b.tag(1, 0).tag(1, 2);
+            });
+            actions.item(|b| {
+                b.tag(1, 3).bytes(b"hi");
+            });
+            actions.item(|b| {
+                b.tag(1, 4);
+            });
+        });
+
+    assert_eq!(
+        builder.finish().unwrap(),
+        from_json::<Proto>(&Value::parse(json).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn owned_messages() {
+    use noser::message::Message;
+    use std::sync::mpsc;
+
+    let json = r#"{"frame":3,"position":{"x":-4,"y":9},"actions":["Jump"]}"#;
+    let arena = from_json::<Proto>(&Value::parse(json).unwrap()).unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut message = Message::<Proto<'static>>::from_bytes(arena).unwrap();
+        message.get_mut().position.y.write(10);
+        sender.send(message).unwrap();
+    });
+
+    let mut message = receiver.recv().unwrap();
+    assert_eq!(message.get().position.y.read(), 10);
+    assert!(freyr::matches!(
+        *message.get().actions.borrow(0),
+        Action::Jump
+    ));
+
+    let mut truncated = message.into_bytes().into_vec();
+    truncated.pop();
+    assert!(Message::<Proto<'static>>::from_bytes(truncated).is_err());
+}
+
+#[test]
+fn dedupe_messages() {
+    use noser::message::Message;
+    use std::collections::HashSet;
+
+    let jsons = [
+        r#"{"frame":3,"position":{"x":-4,"y":9},"actions":["Jump"]}"#,
+        r#"{"frame":3,"position":{"x":-4,"y":9},"actions":[]}"#,
+        r#"{"frame":3,"position":{"x":-4,"y":9},"actions":["Jump"]}"#,
+    ];
+
+    let messages: HashSet<_> = jsons
+        .iter()
+        .map(|json| from_json::<Proto>(&Value::parse(json).unwrap()).unwrap())
+        .map(|arena| Message::<Proto<'static>>::from_bytes(arena).unwrap())
+        .collect();
+    assert_eq!(messages.len(), 2);
+}
+
+#[test]
+fn replicate_snapshots() {
+    use noser::diff::Patch;
+    use noser::message::Message;
+
+    let parse = |json: &str| {
+        let arena = from_json::<Proto>(&Value::parse(json).unwrap()).unwrap();
+        Message::<Proto<'static>>::from_bytes(arena).unwrap()
+    };
+
+    let say = format!(r#"{{"Say":{:?}}}"#, vec![104; 64]);
+    let acked = parse(&format!(
+        r#"{{"frame":3,"position":{{"x":-4,"y":9}},"actions":[{}]}}"#,
+        say
+    ));
+    let latest = parse(&format!(
+        r#"{{"frame":4,"position":{{"x":-4,"y":9}},"actions":[{},"Jump"]}}"#,
+        say
+    ));
+
+    let bytes = acked.diff(&latest).unwrap().to_bytes().unwrap();
+    assert!(bytes.len() < latest.as_bytes().len());
+
+    let replica = acked.patch(&Patch::from_bytes(&bytes).unwrap()).unwrap();
+    assert_eq!(replica, latest);
+}
+
+#[test]
+fn copy_parts_of_messages() {
+    use noser::ffi;
+    use noser::traits::CopyInto;
+    use noser::List;
+
+    let json = r#"{"frame":3,"position":{"x":-4,"y":9},"actions":[{"Move":"West"},{"Say":[104,105]},"Jump"]}"#;
+    let arena = from_json::<Proto>(&Value::parse(json).unwrap()).unwrap();
+
+    assert_eq!(Proto::copy_to_vec(&arena).unwrap(), arena);
+
+    let actions = ffi::skip::<Position>(ffi::skip::<noser::Literal<u8>>(&arena).unwrap()).unwrap();
+    let copy = List::<Action>::copy_to_vec(actions).unwrap();
+    assert_eq!(
+        to_json::<List<Action>>(&copy).unwrap().to_string(),
+        r#"[{"Move":"West"},{"Say":[104,105]},"Jump"]"#
+    );
+}
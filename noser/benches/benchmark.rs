@@ -115,3 +115,21 @@ fn nested_list_read_value_in_4_sublists(b: &mut Bencher) {
         ))
     });
 }
+
+#[bench]
+fn create_buffer(b: &mut Bencher) {
+    let writer = List::<Literal<'_, u64>>::with_capacity(64);
+
+    b.iter(|| test::black_box(writer.create_buffer().unwrap()));
+}
+
+#[bench]
+fn create_buffer_in_pool(b: &mut Bencher) {
+    let writer = List::<Literal<'_, u64>>::with_capacity(64);
+    let mut pool = noser::pool::Pool::new();
+
+    b.iter(|| {
+        let buffer = writer.create_buffer_in(&mut pool).unwrap();
+        pool.recycle(test::black_box(buffer));
+    });
+}
@@ -0,0 +1,230 @@
+//! Writes a message front to back into a growing buffer, for producers that don't know the sizes
+//! of lists and slices before they start. Values are appended in layout order, list lengths are
+//! patched in once the list is closed.
+use alloc::vec::Vec;
+
+use crate::traits::{LiteralInnerType, Write, WriteTypeInfo};
+
+#[derive(Debug, Default)]
+pub struct MessageBuilder {
+    buffer: Vec<u8>,
+    /// The first error, every later call is ignored and `finish` returns it.
+    error: Option<crate::NoserError>,
+}
+
+/// The items of a list opened with [MessageBuilder::list].
+pub struct ListBuilder<'b> {
+    builder: &'b mut MessageBuilder,
+    len: crate::ListLen,
+}
+
+impl MessageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        MessageBuilder {
+            buffer: Vec::with_capacity(capacity),
+            error: None,
+        }
+    }
+
+    /// The number of bytes written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    #[inline]
+    fn append(&mut self, size: usize) -> Option<&mut [u8]> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        let start = self.buffer.len();
+        self.buffer.resize(start + size, 0);
+        Some(&mut self.buffer[start..])
+    }
+
+    #[inline]
+    fn fail(&mut self, error: crate::NoserError) {
+        self.error.get_or_insert(error);
+    }
+
+    #[inline]
+    pub fn literal<T>(&mut self, val: T) -> &mut Self
+    where
+        T: LiteralInnerType + Write,
+    {
+        if let Some(arena) = self.append(T::SIZE) {
+            T::write(arena, val);
+        }
+
+        self
+    }
+
+    /// The tag of an enum variant, its fields are appended after it.
+    #[inline]
+    pub fn tag(&mut self, variant_bytes: usize, tag: u64) -> &mut Self {
+        if let Some(arena) = self.append(variant_bytes) {
+            crate::write_var_len_int(arena, variant_bytes, tag);
+        }
+
+        self
+    }
+
+    /// A byte slice, as read by `&[u8]` and `&mut [u8]`.
+    #[inline]
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        if bytes.len() > crate::Ptr::MAX as usize {
+            self.fail(crate::NoserError::IntegerOverflow);
+        }
+
+        if let Some(arena) = self.append(crate::Ptr::SIZE + bytes.len()) {
+            let (len, contents) = arena.split_at_mut(crate::Ptr::SIZE);
+
+            crate::Ptr::write(len, bytes.len() as crate::Ptr);
+            contents.copy_from_slice(bytes);
+        }
+
+        self
+    }
+
+    /// Appends a value laid out by a writer, for parts whose size is known.
+    #[inline]
+    pub fn imprint<T>(&mut self, writer: &dyn WriteTypeInfo<T>) -> &mut Self {
+        let result = match self.append(writer.result_size() as usize) {
+            Some(arena) => writer.imprint(arena),
+            None => Ok(()),
+        };
+
+        if let Err(e) = result {
+            self.fail(e);
+        }
+
+        self
+    }
+
+    /// A list whose items are appended by `items`, its length is written once it returns.
+    #[inline]
+    pub fn list<F>(&mut self, items: F) -> &mut Self
+    where
+        F: FnOnce(&mut ListBuilder),
+    {
+        let start = self.buffer.len();
+        self.literal::<crate::ListLen>(0);
+
+        let mut list = ListBuilder {
+            builder: self,
+            len: 0,
+        };
+        items(&mut list);
+
+        let len = list.len;
+        if self.error.is_none() {
+            crate::ListLen::write(&mut self.buffer[start..], len);
+        }
+
+        self
+    }
+
+    /// The finished message, or the first error raised while writing it.
+    pub fn finish(self) -> crate::Result<Vec<u8>> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.buffer),
+        }
+    }
+}
+
+impl ListBuilder<'_> {
+    /// Appends one item, `item` writes all of its parts.
+    #[inline]
+    pub fn item<F>(&mut self, item: F) -> &mut Self
+    where
+        F: FnOnce(&mut MessageBuilder),
+    {
+        match self.len.checked_add(1) {
+            Some(len) => self.len = len,
+            None => self.builder.fail(crate::NoserError::IntegerOverflow),
+        }
+
+        item(self.builder);
+        self
+    }
+
+    /// The number of items appended so far.
+    #[inline]
+    pub fn len(&self) -> crate::ListLen {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{Build, DefaultWriter};
+    use crate::{List, Literal};
+
+    #[test]
+    fn literals_and_bytes() {
+        let mut builder = MessageBuilder::new();
+        builder.literal(7u16).bytes(b"hi").tag(1, 2).literal(-1i8);
+
+        assert_eq!(
+            builder.finish().unwrap(),
+            [7, 0, 2, 0, 0, 0, b'h', b'i', 2, 255]
+        );
+    }
+
+    #[test]
+    fn nested_lists() {
+        let mut builder = MessageBuilder::new();
+        builder.list(|outer| {
+            for len in 1..=3u8 {
+                outer.item(|b| {
+                    b.list(|inner| {
+                        for i in 0..len {
+                            inner.item(|b| {
+                                b.literal(i);
+                            });
+                        }
+                    });
+                });
+            }
+        });
+
+        let mut arena = builder.finish().unwrap();
+        let lists = List::<List<Literal<u8>>>::create(&mut arena).unwrap();
+        assert_eq!(lists.capacity(), 3);
+        assert_eq!(lists.borrow(2).capacity(), 3);
+        assert_eq!(lists.borrow(2).borrow(2).read(), 2);
+    }
+
+    #[test]
+    fn matches_writers() {
+        let writer = List::<Literal<u32>>::with_capacity(2);
+
+        let mut builder = MessageBuilder::with_capacity(12);
+        builder.imprint(&writer).list(|list| {
+            list.item(|b| {
+                b.imprint::<Literal<u32>>(Literal::<u32>::writer());
+            });
+        });
+
+        let arena = builder.finish().unwrap();
+        assert_eq!(arena[..12], writer.create_buffer().unwrap()[..]);
+        assert_eq!(arena[12..], [1, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
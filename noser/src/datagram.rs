@@ -0,0 +1,267 @@
+//! Several independently typed messages packed into one datagram, e.g. the input, acks and chat
+//! a client sends in one UDP packet. Every entry is a `u16` type id chosen by the application,
+//! the `u16` length of the message and the message itself.
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::message::Message;
+use crate::prelude::SliceExt;
+use crate::traits::{Build, LiteralInnerType, Read, Reborrow, Write, WriteTypeInfo};
+
+/// The type id and length in front of every message.
+const HEADER_SIZE: usize = 2 * u16::SIZE;
+
+/// Packs messages into a datagram of at most `budget` bytes, e.g. 1200 bytes to stay below the
+/// MTU of most paths.
+#[derive(Debug, Clone)]
+pub struct DatagramPacker {
+    buffer: Vec<u8>,
+    budget: usize,
+}
+
+impl DatagramPacker {
+    pub fn new(budget: usize) -> Self {
+        DatagramPacker {
+            buffer: Vec::with_capacity(budget),
+            budget,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// The bytes left for entries, headers included.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.budget - self.buffer.len()
+    }
+
+    /// Adds the bytes of a message. Returns false if it doesn't fit in the remaining budget, the
+    /// datagram should then be sent and the message added to the next one. Fails with
+    /// [FrameTooLarge](crate::NoserError::FrameTooLarge) if it doesn't fit in any datagram.
+    pub fn push_bytes(&mut self, type_id: u16, message: &[u8]) -> crate::Result<bool> {
+        let start = match self.append_header(type_id, message.len())? {
+            Some(start) => start,
+            None => return Ok(false),
+        };
+
+        self.buffer[start + HEADER_SIZE..].copy_from_slice(message);
+        Ok(true)
+    }
+
+    #[inline]
+    pub fn push_message<T>(&mut self, type_id: u16, message: &Message<T>) -> crate::Result<bool>
+    where
+        T: for<'a> Reborrow<'a>,
+    {
+        self.push_bytes(type_id, message.as_bytes())
+    }
+
+    /// Adds a message laid out by `writer`, straight into the datagram.
+    pub fn imprint<T>(
+        &mut self,
+        type_id: u16,
+        writer: &dyn WriteTypeInfo<T>,
+    ) -> crate::Result<bool> {
+        let start = match self.append_header(type_id, writer.result_size() as usize)? {
+            Some(start) => start,
+            None => return Ok(false),
+        };
+
+        if let Err(e) = writer.imprint(&mut self.buffer[start + HEADER_SIZE..]) {
+            self.buffer.truncate(start);
+            return Err(e);
+        }
+
+        Ok(true)
+    }
+
+    /// The packed datagram, leaving the packer empty for the next one.
+    pub fn finish(&mut self) -> Vec<u8> {
+        core::mem::replace(&mut self.buffer, Vec::with_capacity(self.budget))
+    }
+
+    /// Appends the header and room for a message of `len` bytes, returning where the entry
+    /// starts, or `None` if it doesn't fit.
+    #[inline]
+    fn append_header(&mut self, type_id: u16, len: usize) -> crate::Result<Option<usize>> {
+        let size = HEADER_SIZE + len;
+        if size > self.budget || len > u16::MAX as usize {
+            return Err(crate::NoserError::FrameTooLarge(self.budget));
+        } else if size > self.remaining() {
+            return Ok(None);
+        }
+
+        let start = self.buffer.len();
+        self.buffer.resize(start + size, 0);
+        u16::write(&mut self.buffer[start..], type_id);
+        u16::write(&mut self.buffer[start + u16::SIZE..], len as u16);
+
+        Ok(Some(start))
+    }
+}
+
+/// The type ids and messages of a received datagram.
+pub struct Entries<'a> {
+    rest: &'a mut [u8],
+}
+
+impl<'a> Entries<'a> {
+    pub fn new(datagram: &'a mut [u8]) -> Self {
+        Entries { rest: datagram }
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = crate::Result<(u16, &'a mut [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let rest = core::mem::take(&mut self.rest);
+        let entry = rest
+            .noser_split(HEADER_SIZE as crate::Ptr)
+            .and_then(|(header, rest)| {
+                let len = u16::read(&header[u16::SIZE..]);
+                let (message, rest) = rest.noser_split(crate::Ptr::from(len))?;
+
+                self.rest = rest;
+                Ok((u16::read(header), message))
+            });
+
+        Some(entry)
+    }
+}
+
+type Handler<'h> = Box<dyn FnMut(&mut [u8]) -> crate::Result<()> + 'h>;
+
+/// Hands the messages of datagrams to the handlers registered for their type ids.
+#[derive(Default)]
+pub struct Dispatcher<'h> {
+    handlers: BTreeMap<u16, Handler<'h>>,
+}
+
+impl<'h> Dispatcher<'h> {
+    pub fn new() -> Self {
+        Dispatcher {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    /// Handles the messages with id `type_id` as `T`, the root type with a `'static` arena
+    /// lifetime. Messages are validated before they are handed to `handler`.
+    pub fn on<T, F>(&mut self, type_id: u16, mut handler: F) -> &mut Self
+    where
+        T: for<'a> Reborrow<'a>,
+        F: for<'v> FnMut(<T as Reborrow<'v>>::View) -> crate::Result<()> + 'h,
+    {
+        let handler = move |message: &mut [u8]| {
+            let (unused, view) = <T as Reborrow>::View::build(message)?;
+            if !unused.is_empty() {
+                return Err(crate::NoserError::Malformed);
+            }
+
+            handler(view)
+        };
+
+        self.handlers.insert(type_id, Box::new(handler));
+        self
+    }
+
+    /// Hands every message of `datagram` to its handler, stopping at the first error. Messages
+    /// without a handler are skipped, so peers can send types this side doesn't know yet.
+    /// Returns the number of handled messages.
+    pub fn dispatch(&mut self, datagram: &mut [u8]) -> crate::Result<usize> {
+        let mut handled = 0;
+
+        for entry in Entries::new(datagram) {
+            let (type_id, message) = entry?;
+
+            if let Some(handler) = self.handlers.get_mut(&type_id) {
+                handler(message)?;
+                handled += 1;
+            }
+        }
+
+        Ok(handled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{List, Literal};
+
+    const INPUT: u16 = 1;
+    const ACK: u16 = 2;
+
+    #[test]
+    fn packs_up_to_budget() {
+        let mut packer = DatagramPacker::new(20);
+
+        assert!(packer.push_bytes(INPUT, &[1, 0, 0, 0, 7]).unwrap());
+        assert!(packer.imprint(ACK, &Literal::<u32>::with_value(3)).unwrap());
+        assert_eq!(packer.remaining(), 3);
+        assert!(!packer.push_bytes(ACK, &[]).unwrap());
+        assert!(packer.push_bytes(ACK, &[0; 17]).is_err());
+
+        let datagram = packer.finish();
+        assert!(packer.is_empty());
+        assert_eq!(
+            datagram,
+            [1, 0, 5, 0, 1, 0, 0, 0, 7, 2, 0, 4, 0, 3, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn dispatches_by_type() {
+        let mut packer = DatagramPacker::new(1200);
+        let input: &dyn WriteTypeInfo<Literal<u8>> = &Literal::<u8>::with_value(4);
+        packer.imprint(INPUT, &List::from(&[input])).unwrap();
+        packer.push_bytes(9, &[1, 2, 3]).unwrap();
+        packer.imprint(ACK, &Literal::<u32>::with_value(8)).unwrap();
+        packer.imprint(ACK, &Literal::<u32>::with_value(9)).unwrap();
+        let mut datagram = packer.finish();
+
+        let (mut inputs, mut acks) = (vec![], vec![]);
+        let handled = Dispatcher::new()
+            .on::<List<Literal<u8>>, _>(INPUT, |list| {
+                inputs.push(list.borrow(0).read());
+                Ok(())
+            })
+            .on::<Literal<u32>, _>(ACK, |ack| {
+                acks.push(ack.read());
+                Ok(())
+            })
+            .dispatch(&mut datagram)
+            .unwrap();
+
+        assert_eq!(handled, 3);
+        assert_eq!(inputs, [4]);
+        assert_eq!(acks, [8, 9]);
+    }
+
+    #[test]
+    fn rejects_bad_datagrams() {
+        let mut dispatcher = Dispatcher::new();
+        dispatcher.on::<Literal<u32>, _>(ACK, |_| Ok(()));
+
+        assert!(dispatcher.dispatch(&mut [2, 0, 4, 0, 1, 0, 0]).is_err());
+        assert!(dispatcher.dispatch(&mut [2, 0, 2, 0, 1, 0]).is_err());
+        assert!(dispatcher
+            .dispatch(&mut [2, 0, 5, 0, 1, 0, 0, 0, 0])
+            .is_err());
+        assert!(dispatcher.dispatch(&mut [2, 0]).is_err());
+        assert_eq!(dispatcher.dispatch(&mut []).unwrap(), 0);
+    }
+}
@@ -0,0 +1,259 @@
+//! Patches carrying only the changes between two buffers of the same type, e.g. to replicate a
+//! snapshot by sending what changed since the last acknowledged state.
+use alloc::{vec, vec::Vec};
+
+use crate::builder::MessageBuilder;
+use crate::prelude::SliceExt;
+use crate::traits::{Diff, LiteralInnerType, Read};
+
+/// Replaces `removed` bytes at offset `at` of the old buffer with `inserted`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Splice {
+    pub at: crate::Ptr,
+    pub removed: crate::Ptr,
+    pub inserted: Vec<u8>,
+}
+
+/// The changes turning one buffer into another, as splices of the old buffer ordered by offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    base_len: crate::Ptr,
+    splices: Vec<Splice>,
+}
+
+impl Patch {
+    /// An empty patch for an old buffer of `base_len` bytes.
+    pub fn new(base_len: crate::Ptr) -> Self {
+        Patch {
+            base_len,
+            splices: vec![],
+        }
+    }
+
+    /// The length of the buffer the patch applies to.
+    #[inline]
+    pub fn base_len(&self) -> crate::Ptr {
+        self.base_len
+    }
+
+    #[inline]
+    pub fn splices(&self) -> &[Splice] {
+        &self.splices
+    }
+
+    /// True if the buffers were equal.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.splices.is_empty()
+    }
+
+    /// Records that the first `old_size` bytes of `old` become the first `new_size` bytes of
+    /// `new`, returning the rests of both arenas. Bytes both regions start or end with are left
+    /// out, and a splice right after the last one is merged into it.
+    pub fn record<'o, 'n>(
+        &mut self,
+        old: &'o [u8],
+        old_size: crate::Ptr,
+        new: &'n [u8],
+        new_size: crate::Ptr,
+    ) -> crate::Result<(&'o [u8], &'n [u8])> {
+        let offset = (self.base_len as usize)
+            .checked_sub(old.len())
+            .ok_or(crate::NoserError::Malformed)?;
+
+        let (removed, old_rest) = old.noser_split_imut(old_size)?;
+        let (inserted, new_rest) = new.noser_split_imut(new_size)?;
+
+        let prefix = common(removed.iter(), inserted.iter());
+        let (removed, inserted) = (&removed[prefix..], &inserted[prefix..]);
+        let suffix = common(removed.iter().rev(), inserted.iter().rev());
+        let removed = &removed[..removed.len() - suffix];
+        let inserted = &inserted[..inserted.len() - suffix];
+
+        if !removed.is_empty() || !inserted.is_empty() {
+            self.push(
+                (offset + prefix) as crate::Ptr,
+                removed.len() as crate::Ptr,
+                inserted,
+            )?;
+        }
+
+        Ok((old_rest, new_rest))
+    }
+
+    #[inline]
+    pub(crate) fn push(
+        &mut self,
+        at: crate::Ptr,
+        removed: crate::Ptr,
+        inserted: &[u8],
+    ) -> crate::Result<()> {
+        if let Some(last) = self.splices.last_mut() {
+            let end = last.at + last.removed;
+
+            if at < end {
+                return Err(crate::NoserError::Malformed);
+            } else if at == end {
+                last.removed += removed;
+                last.inserted.extend_from_slice(inserted);
+                return Ok(());
+            }
+        }
+
+        self.splices.push(Splice {
+            at,
+            removed,
+            inserted: inserted.to_vec(),
+        });
+        Ok(())
+    }
+
+    /// The patch as a noser message: the length of the old buffer followed by a list of
+    /// splices, each an offset, the number of removed bytes and the inserted bytes.
+    pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        let mut builder = MessageBuilder::new();
+        builder.literal(self.base_len).list(|list| {
+            for splice in &self.splices {
+                list.item(|b| {
+                    b.literal(splice.at)
+                        .literal(splice.removed)
+                        .bytes(&splice.inserted);
+                });
+            }
+        });
+
+        builder.finish()
+    }
+
+    /// Reads a patch written by `to_bytes`. Whether its splices fit the old buffer is checked
+    /// by [apply_patch].
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        let (base_len, bytes) = read_ptr(bytes)?;
+        let (len, mut bytes) = read_ptr(bytes)?;
+
+        let mut splices = vec![];
+        for _ in 0..len {
+            let (at, rest) = read_ptr(bytes)?;
+            let (removed, rest) = read_ptr(rest)?;
+            let (size, rest) = read_ptr(rest)?;
+            let (inserted, rest) = rest.noser_split_imut(size)?;
+
+            splices.push(Splice {
+                at,
+                removed,
+                inserted: inserted.to_vec(),
+            });
+            bytes = rest;
+        }
+
+        if !bytes.is_empty() {
+            return Err(crate::NoserError::Malformed);
+        }
+
+        Ok(Patch { base_len, splices })
+    }
+}
+
+#[inline]
+fn common<'a>(a: impl Iterator<Item = &'a u8>, b: impl Iterator<Item = &'a u8>) -> usize {
+    a.zip(b).take_while(|(a, b)| a == b).count()
+}
+
+#[inline]
+fn read_ptr(bytes: &[u8]) -> crate::Result<(crate::Ptr, &[u8])> {
+    let (ptr, rest) = bytes.noser_split_imut(crate::Ptr::SIZE as crate::Ptr)?;
+    Ok((crate::Ptr::read(ptr), rest))
+}
+
+/// The changes from `old` to `new`, which must each hold exactly one `T`.
+pub fn diff<T: Diff>(old: &[u8], new: &[u8]) -> crate::Result<Patch> {
+    if old.len() > crate::Ptr::MAX as usize {
+        return Err(crate::NoserError::IntegerOverflow);
+    }
+
+    let mut patch = Patch::new(old.len() as crate::Ptr);
+
+    let (old_rest, new_rest) = T::diff(old, new, &mut patch)?;
+    if !old_rest.is_empty() || !new_rest.is_empty() {
+        return Err(crate::NoserError::Malformed);
+    }
+
+    Ok(patch)
+}
+
+/// The buffer `patch` was made for, given the buffer it was made from. The result is not
+/// validated, use e.g. [Message::from_bytes](crate::message::Message::from_bytes) for that.
+pub fn apply_patch(old: &[u8], patch: &Patch) -> crate::Result<Vec<u8>> {
+    if old.len() != patch.base_len as usize {
+        return Err(crate::NoserError::Malformed);
+    }
+
+    let mut new = Vec::with_capacity(old.len());
+    let mut cursor = 0;
+
+    for splice in &patch.splices {
+        let at = splice.at as usize;
+        let end = at
+            .checked_add(splice.removed as usize)
+            .ok_or(crate::NoserError::IntegerOverflow)?;
+
+        if at < cursor || end > old.len() {
+            return Err(crate::NoserError::Malformed);
+        }
+
+        new.extend_from_slice(&old[cursor..at]);
+        new.extend_from_slice(&splice.inserted);
+        cursor = end;
+    }
+
+    new.extend_from_slice(&old[cursor..]);
+    Ok(new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_trims_and_merges() {
+        let old = [1, 2, 3, 4, 5, 6];
+        let new = [1, 9, 8, 7, 8, 5, 6];
+
+        let mut patch = Patch::new(old.len() as crate::Ptr);
+        let (old_rest, new_rest) = patch.record(&old, 3, &new, 3).unwrap();
+        patch.record(old_rest, 3, new_rest, 4).unwrap();
+
+        assert_eq!(
+            patch.splices(),
+            [Splice {
+                at: 1,
+                removed: 3,
+                inserted: vec![9, 8, 7, 8],
+            }]
+        );
+        assert_eq!(apply_patch(&old, &patch).unwrap(), new);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut patch = Patch::new(8);
+        patch.push(1, 2, &[3]).unwrap();
+        patch.push(6, 0, &[4, 5]).unwrap();
+
+        let bytes = patch.to_bytes().unwrap();
+        assert_eq!(Patch::from_bytes(&bytes).unwrap(), patch);
+        assert!(Patch::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn rejects_misplaced_splices() {
+        let mut patch = Patch::new(4);
+        patch.push(2, 2, &[]).unwrap();
+
+        assert!(patch.push(1, 0, &[]).is_err());
+        assert!(apply_patch(&[0; 3], &patch).is_err());
+
+        patch.splices[0].removed = 3;
+        assert!(apply_patch(&[0; 4], &patch).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Tracks which bytes of a buffer were written through its views, so the changes can be sent
+//! without diffing the whole buffer. Only writes made inside [Dirty::track] are seen, code writing
+//! a tracked buffer through a plain `&mut [u8]` reports the bytes with [mark].
+use std::ops::Range;
+
+use crate::diff::Patch;
+
+/// The byte ranges of a buffer written since the last [Dirty::clear_dirty], ordered and merged.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Dirty {
+    ranges: Vec<Range<crate::Ptr>>,
+}
+
+impl Dirty {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn ranges(&self) -> &[Range<crate::Ptr>] {
+        &self.ranges
+    }
+
+    /// True if nothing was written since the last `clear_dirty`.
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    #[inline]
+    pub fn clear_dirty(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// Marks `range` as written, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<crate::Ptr>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.ranges[first].start);
+            merged.end = merged.end.max(self.ranges[last - 1].end);
+        }
+
+        self.ranges.splice(first..last, Some(merged));
+    }
+
+    /// Adds the ranges of `other`, e.g. to collect the writes of several updates for a client.
+    pub fn merge(&mut self, other: &Dirty) {
+        for range in &other.ranges {
+            self.insert(range.clone());
+        }
+    }
+
+    /// Runs `f` on `arena`, marking the bytes written through views of it on this thread. The
+    /// views can't outlive `f`, writes after `track` returns would not be marked.
+    pub fn track<F, R>(&mut self, arena: &mut [u8], f: F) -> R
+    where
+        F: FnOnce(&mut [u8]) -> R,
+    {
+        crate::hooks::watch(self, arena, f)
+    }
+
+    /// A patch replacing the dirty ranges of the buffer they were recorded for with their
+    /// current contents in `buffer`.
+    pub fn patch(&self, buffer: &[u8]) -> crate::Result<Patch> {
+        if buffer.len() > crate::Ptr::MAX as usize {
+            return Err(crate::NoserError::IntegerOverflow);
+        }
+
+        let mut patch = Patch::new(buffer.len() as crate::Ptr);
+        for range in &self.ranges {
+            let written = buffer
+                .get(range.start as usize..range.end as usize)
+                .ok_or(crate::NoserError::Malformed)?;
+
+            patch.push(range.start, range.end - range.start, written)?;
+        }
+
+        Ok(patch)
+    }
+}
+
+/// Marks `written` as dirty in every tracked buffer containing it.
+#[inline]
+pub fn mark(written: &[u8]) {
+    crate::hooks::each(written, |dirty: &mut Dirty, offset| {
+        dirty.insert(offset..offset + written.len() as crate::Ptr)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_merges() {
+        let mut dirty = Dirty::new();
+        dirty.insert(4..6);
+        dirty.insert(10..12);
+        dirty.insert(0..1);
+        dirty.insert(6..7);
+        dirty.insert(3..3);
+        assert_eq!(dirty.ranges(), [0..1, 4..7, 10..12]);
+
+        dirty.insert(2..11);
+        assert_eq!(dirty.ranges(), [0..1, 2..12]);
+
+        dirty.clear_dirty();
+        assert!(dirty.is_clean());
+    }
+
+    #[test]
+    fn track_marks() {
+        let mut arena = [0u8; 8];
+        let mut dirty = Dirty::new();
+        dirty.insert(0..1);
+
+        let outside = [0u8; 2];
+        dirty.track(&mut arena, |arena| {
+            mark(&arena[2..4]);
+            mark(&outside);
+        });
+        mark(&arena[6..]);
+
+        assert_eq!(dirty.ranges(), [0..1, 2..4]);
+
+        let patch = dirty.patch(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        assert_eq!(
+            crate::diff::apply_patch(&[0; 8], &patch).unwrap(),
+            [1, 0, 3, 4, 0, 0, 0, 0]
+        );
+        assert!(dirty.patch(&[0; 3]).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+//! Support code for C-ABI accessors, as generated by `noser-schema`. The accessors never build
+//! noser types, they only walk the layout with `Sizeable` and hand out views into the buffer, so
+//! a C caller can read a message without copying it.
+use crate::prelude::SliceExt;
+use crate::traits::{size::Sizeable, LiteralInnerType, Read};
+
+/// A borrowed region of a buffer. Accessors return a view with a null `ptr` when the buffer is
+/// malformed or, for enum payloads, holds a different variant.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct View {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+impl View {
+    pub const NULL: View = View {
+        ptr: core::ptr::null(),
+        len: 0,
+    };
+
+    pub fn new(arena: &[u8]) -> View {
+        View {
+            ptr: arena.as_ptr(),
+            len: arena.len(),
+        }
+    }
+
+    /// The view of a successful walk, or `View::NULL`.
+    pub fn wrap(arena: crate::Result<&[u8]>) -> View {
+        arena.map(View::new).unwrap_or(View::NULL)
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must be null or valid for reads of `len` bytes for the returned lifetime.
+    pub unsafe fn as_slice<'a>(self) -> crate::Result<&'a [u8]> {
+        if self.ptr.is_null() {
+            Err(crate::NoserError::Malformed)
+        } else {
+            Ok(core::slice::from_raw_parts(self.ptr, self.len))
+        }
+    }
+}
+
+/// The bytes of a `T` at the start of the arena.
+#[inline]
+pub fn field<T: Sizeable>(arena: &[u8]) -> crate::Result<&[u8]> {
+    let size = T::in_bounds(arena)?;
+    Ok(&arena[..size as usize])
+}
+
+/// The arena following a `T`.
+#[inline]
+pub fn skip<T: Sizeable>(arena: &[u8]) -> crate::Result<&[u8]> {
+    let size = T::in_bounds(arena)?;
+    Ok(&arena[size as usize..])
+}
+
+/// Reads the tag of an enum, returning it with the payload.
+#[inline]
+pub fn tag(arena: &[u8], variant_bytes: usize) -> crate::Result<(u64, &[u8])> {
+    let (tag, payload) = arena.noser_split_imut(variant_bytes as crate::Ptr)?;
+    Ok((crate::read_var_len_int(tag, variant_bytes), payload))
+}
+
+/// The payload of an enum if it holds the expected variant.
+#[inline]
+pub fn variant(arena: &[u8], variant_bytes: usize, expected: u64) -> crate::Result<&[u8]> {
+    match tag(arena, variant_bytes)? {
+        (tag, payload) if tag == expected => Ok(payload),
+        _ => Err(crate::NoserError::Malformed),
+    }
+}
+
+#[inline]
+pub fn list_len(arena: &[u8]) -> crate::Result<crate::ListLen> {
+    crate::ListLen::read_safe(arena)
+}
+
+/// The bytes of item `idx` of a `List<T>`.
+#[inline]
+pub fn list_get<T: Sizeable>(arena: &[u8], idx: crate::ListLen) -> crate::Result<&[u8]> {
+    if idx >= list_len(arena)? {
+        return Err(crate::NoserError::Malformed);
+    }
+
+    let mut arena = &arena[crate::ListLen::SIZE..];
+    for _ in 0..idx {
+        arena = skip::<T>(arena)?;
+    }
+
+    field::<T>(arena)
+}
+
+/// The contents of a byte slice, without the length.
+#[inline]
+pub fn bytes(arena: &[u8]) -> crate::Result<&[u8]> {
+    Ok(&field::<&[u8]>(arena)?[crate::Ptr::SIZE..])
+}
+
+/// Reads a literal into `out`, returning whether it succeeded.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[inline]
+pub unsafe fn read<T: Read>(arena: crate::Result<&[u8]>, out: *mut T::Output) -> bool {
+    match arena.and_then(T::read_safe) {
+        Ok(val) => {
+            *out = val;
+            true
+        }
+        Err(_) => false,
+    }
+}
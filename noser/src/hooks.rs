@@ -0,0 +1,64 @@
+//! The arenas watched on this thread. [Literal::write](crate::Literal::write) and the
+//! `reinterpret` methods of the enum wrappers look up the arenas containing the bytes they write
+//! here, for [Dirty::track](crate::dirty::Dirty::track) and
+//! [Transaction::apply](crate::transaction::Transaction::apply) alike.
+use std::any::Any;
+use std::cell::RefCell;
+
+struct Watched {
+    start: usize,
+    len: usize,
+    state: Box<dyn Any>,
+}
+
+thread_local! {
+    static WATCHED: RefCell<Vec<Watched>> = const { RefCell::new(vec![]) };
+}
+
+/// Takes the state back when its `watch` call returns, even if it panics.
+struct Unwatch<'s, T: Any>(&'s mut T);
+
+impl<T: Any> Drop for Unwatch<'_, T> {
+    fn drop(&mut self) {
+        let watched = WATCHED.with(|stack| stack.borrow_mut().pop());
+        if let Some(state) = watched.and_then(|watched| watched.state.downcast::<T>().ok()) {
+            *self.0 = *state;
+        }
+    }
+}
+
+/// Runs `f` on `arena` while `state` watches it, `state` holds what [each] recorded afterwards.
+pub(crate) fn watch<T, F, R>(state: &mut T, arena: &mut [u8], f: F) -> R
+where
+    T: Any + Default,
+    F: FnOnce(&mut [u8]) -> R,
+{
+    let watched = Watched {
+        start: arena.as_ptr() as usize,
+        len: arena.len(),
+        state: Box::new(std::mem::take(state)),
+    };
+    WATCHED.with(|stack| stack.borrow_mut().push(watched));
+
+    let _unwatch = Unwatch(state);
+    f(arena)
+}
+
+/// Calls `record` for every `T` watching an arena that contains `bytes`, with the offset of
+/// `bytes` in that arena.
+pub(crate) fn each<T: Any>(bytes: &[u8], mut record: impl FnMut(&mut T, crate::Ptr)) {
+    if bytes.is_empty() {
+        return;
+    }
+
+    let at = bytes.as_ptr() as usize;
+    WATCHED.with(|stack| {
+        for watched in stack.borrow_mut().iter_mut() {
+            if at >= watched.start && at + bytes.len() <= watched.start + watched.len {
+                if let Some(state) = watched.state.downcast_mut::<T>() {
+                    record(state, (at - watched.start) as crate::Ptr);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,125 @@
+//! `Duration` as a literal: the whole seconds as a `u64` followed by the nanoseconds as a `u32`.
+use core::cmp::Ordering;
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec};
+
+#[cfg(feature = "alloc")]
+use crate::json::{JsonError, Value};
+#[cfg(feature = "alloc")]
+use crate::traits::JsonLiteral;
+use crate::traits::{Floats, LiteralInnerType, Read, StructuralLiteral, Write};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+impl LiteralInnerType for Duration {
+    const SIZE: usize = u64::SIZE + u32::SIZE;
+
+    #[inline]
+    fn imprint(arena: &mut [u8]) -> crate::Result<()> {
+        if arena.len() >= Self::SIZE {
+            Ok(())
+        } else {
+            Err(crate::NoserError::undersized(Self::SIZE, arena))
+        }
+    }
+
+    #[inline]
+    fn validate(arena: &[u8]) -> crate::Result<()> {
+        match Self::read(arena) {
+            Some(_) => Ok(()),
+            None => Err(crate::NoserError::Malformed),
+        }
+    }
+}
+
+impl Write for Duration {
+    #[inline]
+    fn write(arena: &mut [u8], val: Duration) {
+        u64::write(arena, val.as_secs());
+        u32::write(&mut arena[u64::SIZE..], val.subsec_nanos());
+    }
+}
+
+impl Read for Duration {
+    /// None if the nanoseconds are a second or more, which built literals never are.
+    type Output = Option<Duration>;
+
+    #[inline]
+    fn read(arena: &[u8]) -> Option<Duration> {
+        let nanos = u32::read(&arena[u64::SIZE..]);
+
+        if nanos < NANOS_PER_SEC {
+            Some(Duration::new(u64::read(arena), nanos))
+        } else {
+            None
+        }
+    }
+}
+
+impl StructuralLiteral for Duration {
+    #[inline]
+    fn compare(a: Option<Duration>, b: Option<Duration>, _: Floats) -> Option<Ordering> {
+        Some(a.cmp(&b))
+    }
+}
+
+/// Written like serde does, as `{"secs":..,"nanos":..}`, so no precision is lost.
+#[cfg(feature = "alloc")]
+impl JsonLiteral for Duration {
+    #[inline]
+    fn to_value(val: Option<Duration>) -> Value {
+        val.map(|d| {
+            Value::Object(vec![
+                (String::from("secs"), Value::UInt(d.as_secs())),
+                (
+                    String::from("nanos"),
+                    Value::UInt(u64::from(d.subsec_nanos())),
+                ),
+            ])
+        })
+        .unwrap_or(Value::Null)
+    }
+
+    #[inline]
+    fn from_value(value: &Value) -> crate::Result<Duration> {
+        let secs = u64::from_value(value.field("secs")?)?;
+        let nanos = u32::from_value(value.field("nanos")?)?;
+
+        if nanos >= NANOS_PER_SEC {
+            return Err(JsonError::OutOfRange.into());
+        }
+
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rw_duration() {
+        let arena = &mut [0; 12];
+
+        Duration::write(arena, Duration::new(3, 250));
+        assert_eq!(arena, &[3, 0, 0, 0, 0, 0, 0, 0, 250, 0, 0, 0]);
+        assert_eq!(Duration::read(arena), Some(Duration::new(3, 250)));
+
+        u32::write(&mut arena[8..], NANOS_PER_SEC);
+        assert_eq!(Duration::read(arena), None);
+    }
+
+    #[test]
+    fn json_duration() {
+        let duration = Duration::new(u64::MAX, NANOS_PER_SEC - 1);
+        let value = Duration::to_value(Some(duration));
+
+        assert_eq!(Duration::from_value(&value).unwrap(), duration);
+        assert_eq!(Duration::to_value(None), Value::Null);
+
+        let value = Value::parse(r#"{"secs":1,"nanos":1000000000}"#).unwrap();
+        assert!(Duration::from_value(&value).is_err());
+    }
+}
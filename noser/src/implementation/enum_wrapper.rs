@@ -1,11 +1,22 @@
+#[cfg(feature = "alloc")]
+use crate::diff::Patch;
+#[cfg(feature = "alloc")]
+use crate::json::Value;
 use crate::prelude::SliceExt;
-use crate::traits::{StaticEnum, Tagged};
+use crate::traits::{
+    raw_copy_into, raw_copy_size, size::Sizeable, Build, CopyInto, DynamicEnum, Floats,
+    LiteralInnerType, Read, Reborrow, StaticEnum, Structural, Tagged, Write,
+};
+#[cfg(feature = "alloc")]
+use crate::traits::{Diff, Json};
+use core::cmp::Ordering;
+use core::hash::Hasher;
 
-const U64LEN: usize = ::std::mem::size_of::<u64>();
+const U64LEN: usize = ::core::mem::size_of::<u64>();
 
 #[inline]
 pub fn write_var_len_int(buffer: &mut [u8], len: usize, int: u64) {
-    let int = unsafe { ::std::mem::transmute::<u64, [u8; U64LEN]>(int.to_le()) };
+    let int = unsafe { ::core::mem::transmute::<u64, [u8; U64LEN]>(int.to_le()) };
 
     buffer[..len].clone_from_slice(&int[..len]);
 }
@@ -16,7 +27,7 @@ pub fn read_var_len_int(buffer: &[u8], len: usize) -> u64 {
 
     int[..len].clone_from_slice(&buffer[..len]);
 
-    u64::from_le(unsafe { ::std::mem::transmute::<[u8; U64LEN], u64>(int) })
+    u64::from_le(unsafe { ::core::mem::transmute::<[u8; U64LEN], u64>(int) })
 }
 
 pub struct EnumWrapper<'a, E> {
@@ -52,7 +63,13 @@ where
 
         match inner {
             Ok(inner) => {
+                #[cfg(feature = "transaction")]
+                crate::transaction::journal(self.variant_bytes);
+
                 write_var_len_int(self.variant_bytes, E::VARIANT_BYTES, to.variant_tag());
+                #[cfg(feature = "dirty")]
+                crate::dirty::mark(self.variant_bytes);
+
                 self.inner = inner;
                 Ok(self)
             }
@@ -126,3 +143,381 @@ where
         )
     }
 }
+
+impl<'a, E> Reborrow<'a> for EnumWrapper<'static, E>
+where
+    E: Reborrow<'a>,
+    E::View: StaticEnum<'a>,
+{
+    type View = EnumWrapper<'a, E::View>;
+}
+
+impl<'a, E> CopyInto for EnumWrapper<'a, E>
+where
+    E: StaticEnum<'a>,
+{
+    #[inline]
+    fn copy_size(from: &[u8]) -> crate::Result<(crate::Ptr, &[u8])> {
+        raw_copy_size::<Self>(from)
+    }
+
+    #[inline]
+    fn copy_into<'f, 't>(
+        from: &'f [u8],
+        to: &'t mut [u8],
+    ) -> crate::Result<(&'f [u8], &'t mut [u8])> {
+        raw_copy_into::<Self>(from, to)
+    }
+}
+
+/// A switched variant is recorded byte for byte, so only the bytes that differ are sent.
+#[cfg(feature = "alloc")]
+impl<'a, E> Diff for EnumWrapper<'a, E>
+where
+    E: StaticEnum<'a> + Diff,
+{
+    #[inline]
+    fn diff<'o, 'n>(
+        old: &'o [u8],
+        new: &'n [u8],
+        patch: &mut Patch,
+    ) -> crate::Result<(&'o [u8], &'n [u8])> {
+        let size = <E as StaticEnum>::static_size();
+        variant_diff::<E>(old, new, size, E::VARIANT_BYTES, patch)
+    }
+}
+
+/// Records the changes of an enum padded to `size` bytes.
+#[cfg(feature = "alloc")]
+#[inline]
+fn variant_diff<'o, 'n, E: Diff>(
+    old: &'o [u8],
+    new: &'n [u8],
+    size: crate::Ptr,
+    variant_bytes: usize,
+    patch: &mut Patch,
+) -> crate::Result<(&'o [u8], &'n [u8])> {
+    let (old_region, _) = old.noser_split_imut(size)?;
+    let (new_region, _) = new.noser_split_imut(size)?;
+
+    if old_region.get(..variant_bytes) != new_region.get(..variant_bytes) {
+        return patch.record(old, size, new, size);
+    }
+
+    let (old_rest, new_rest) = E::diff(old, new, patch)?;
+    let old_padding = padding(size, old, old_rest)?;
+    let new_padding = padding(size, new, new_rest)?;
+    patch.record(old_rest, old_padding, new_rest, new_padding)
+}
+
+/// The bytes left of a region of `size` bytes at the start of `arena`, after the enum in it
+/// left `rest` unused.
+#[cfg(feature = "alloc")]
+#[inline]
+fn padding(size: crate::Ptr, arena: &[u8], rest: &[u8]) -> crate::Result<crate::Ptr> {
+    size.checked_sub((arena.len() - rest.len()) as crate::Ptr)
+        .ok_or(crate::NoserError::Malformed)
+}
+
+impl<'a, E> Structural for EnumWrapper<'a, E>
+where
+    E: StaticEnum<'a> + Structural,
+{
+    #[inline]
+    fn compare<'l, 'r>(
+        a: &'l [u8],
+        b: &'r [u8],
+        floats: Floats,
+    ) -> crate::Result<(Option<Ordering>, &'l [u8], &'r [u8])> {
+        let (a, rest_a) = a.noser_split_imut(E::static_size())?;
+        let (b, rest_b) = b.noser_split_imut(E::static_size())?;
+
+        E::compare(a, b, floats).map(|(ordering, _, _)| (ordering, rest_a, rest_b))
+    }
+
+    #[inline]
+    fn hash<'l, H: Hasher>(arena: &'l [u8], state: &mut H) -> crate::Result<&'l [u8]> {
+        let (left, right) = arena.noser_split_imut(E::static_size())?;
+
+        E::hash(left, state)?;
+        Ok(right)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E> Json for EnumWrapper<'a, E>
+where
+    E: StaticEnum<'a> + Json,
+{
+    #[inline]
+    fn to_json(arena: &[u8]) -> crate::Result<(Value, &[u8])> {
+        let (left, right) = arena.noser_split_imut(E::static_size())?;
+
+        E::to_json(left).map(|(value, _)| (value, right))
+    }
+
+    #[inline]
+    fn json_size(value: &Value) -> crate::Result<crate::Ptr> {
+        E::json_size(value)?;
+        Ok(E::static_size())
+    }
+
+    #[inline]
+    fn imprint_json<'n>(value: &Value, arena: &'n mut [u8]) -> crate::Result<&'n mut [u8]> {
+        let (left, right) = arena.noser_split(E::static_size())?;
+
+        E::imprint_json(value, left)?;
+        Ok(right)
+    }
+}
+
+/// Keeps a dynamically sized enum in a fixed footprint, so its variant can be changed in place.
+/// The footprint is written in front of the enum, any bytes the current variant does not use
+/// are padding.
+pub struct DynamicEnumWrapper<'a, E> {
+    inner: freyr::AliasGuard<&'a mut [u8], E>,
+}
+
+impl<'a, E> DynamicEnumWrapper<'a, E>
+where
+    E: DynamicEnum<'a>,
+{
+    /// The number of bytes available to the enum, tag included.
+    #[inline]
+    pub fn footprint(&self) -> usize {
+        self.inner.first().len()
+    }
+
+    /// Switches to the variant `to` with a zeroed payload, so lists and slices in it are empty.
+    /// Fails with [DoesNotFit](crate::NoserError::DoesNotFit) if that needs more than the
+    /// footprint, leaving the enum unchanged. The zeroed variant is laid out in a 256 byte stack
+    /// scratch so this never allocates, variants needing more than that never fit.
+    #[inline]
+    pub fn reinterpret(self, to: &E::VariantEnum) -> Result<Self, (Self, crate::NoserError)> {
+        let footprint = self.footprint();
+
+        if footprint < E::VARIANT_BYTES {
+            return Err((self, crate::NoserError::DoesNotFit(footprint)));
+        }
+
+        if !zeroed_fits::<E>(to.variant_tag(), footprint) {
+            return Err((self, crate::NoserError::DoesNotFit(footprint)));
+        }
+
+        let arena = self.inner.move_first();
+        #[cfg(feature = "transaction")]
+        crate::transaction::journal(arena);
+
+        arena.fill(0);
+        write_var_len_int(arena, E::VARIANT_BYTES, to.variant_tag());
+        #[cfg(feature = "dirty")]
+        crate::dirty::mark(arena);
+
+        Ok(DynamicEnumWrapper {
+            inner: freyr::AliasGuard::new(arena, |arena| {
+                E::create(arena).expect("The zeroed variant was checked to fit!")
+            }),
+        })
+    }
+}
+
+/// The bytes `zeroed_fits` lays out a zeroed variant in.
+const ZEROED_SCRATCH: usize = 256;
+
+/// True if the variant `tag` with a zeroed payload fits in `footprint` bytes.
+#[inline]
+fn zeroed_fits<'a, E: DynamicEnum<'a>>(tag: u64, footprint: usize) -> bool {
+    let mut scratch = [0; ZEROED_SCRATCH];
+    let zeroed = &mut scratch[..footprint.min(ZEROED_SCRATCH)];
+    write_var_len_int(zeroed, E::VARIANT_BYTES, tag);
+
+    matches!(E::read_size(zeroed), Ok(size) if size as usize <= footprint)
+}
+
+impl<'a, E> core::ops::Deref for DynamicEnumWrapper<'a, E> {
+    type Target = E;
+
+    #[inline]
+    fn deref(&self) -> &E {
+        self.inner.second()
+    }
+}
+
+impl<'a, E> core::ops::DerefMut for DynamicEnumWrapper<'a, E> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut E {
+        self.inner.mut_second()
+    }
+}
+
+impl<'a, E> Sizeable for DynamicEnumWrapper<'a, E>
+where
+    E: DynamicEnum<'a>,
+{
+    type Strategy = crate::traits::size::Dynamic;
+
+    #[inline]
+    fn read_size(arena: &[u8]) -> crate::traits::size::ReadReturn<Self> {
+        crate::Ptr::read_safe(arena)?
+            .checked_add(crate::Ptr::SIZE as crate::Ptr)
+            .ok_or(crate::NoserError::IntegerOverflow)
+    }
+}
+
+unsafe impl<'a, E> Build<'a> for DynamicEnumWrapper<'a, E>
+where
+    E: DynamicEnum<'a>,
+{
+    #[inline]
+    fn build<'w>(arena: &'w mut [u8]) -> crate::Result<(&'w mut [u8], Self)>
+    where
+        'w: 'a,
+    {
+        let (footprint, arena) = arena.noser_split(crate::Ptr::SIZE as crate::Ptr)?;
+        let (arena, right) = arena.noser_split(crate::Ptr::read(footprint))?;
+
+        let inner =
+            freyr::AliasGuard::try_new(arena, |arena| E::create(arena)).map_err(|(_, e)| e)?;
+
+        Ok((right, DynamicEnumWrapper { inner }))
+    }
+
+    #[inline]
+    fn unchecked_build<'w>(arena: &'w mut [u8]) -> (&'w mut [u8], Self)
+    where
+        'w: 'a,
+    {
+        let (footprint, arena) = arena.split_at_mut(crate::Ptr::SIZE);
+        let (arena, right) = arena.split_at_mut(crate::Ptr::read(footprint) as usize);
+
+        let inner = freyr::AliasGuard::new(arena, |arena| E::unchecked_create(arena));
+
+        (right, DynamicEnumWrapper { inner })
+    }
+}
+
+impl<'a, E> Reborrow<'a> for DynamicEnumWrapper<'static, E>
+where
+    E: Reborrow<'a>,
+    E::View: DynamicEnum<'a>,
+{
+    type View = DynamicEnumWrapper<'a, E::View>;
+}
+
+/// Copies only keep room for the current variant.
+impl<'a, E> CopyInto for DynamicEnumWrapper<'a, E>
+where
+    E: DynamicEnum<'a> + CopyInto,
+{
+    #[inline]
+    fn copy_size(from: &[u8]) -> crate::Result<(crate::Ptr, &[u8])> {
+        let (footprint, from) = from.noser_split_imut(crate::Ptr::SIZE as crate::Ptr)?;
+        let (inner, rest) = from.noser_split_imut(crate::Ptr::read(footprint))?;
+        let (size, _) = E::copy_size(inner)?;
+
+        size.checked_add(crate::Ptr::SIZE as crate::Ptr)
+            .map(|size| (size, rest))
+            .ok_or(crate::NoserError::IntegerOverflow)
+    }
+
+    #[inline]
+    fn copy_into<'f, 't>(
+        from: &'f [u8],
+        to: &'t mut [u8],
+    ) -> crate::Result<(&'f [u8], &'t mut [u8])> {
+        let (footprint, from) = from.noser_split_imut(crate::Ptr::SIZE as crate::Ptr)?;
+        let (inner, rest) = from.noser_split_imut(crate::Ptr::read(footprint))?;
+        let (size, _) = E::copy_size(inner)?;
+
+        let (footprint, to) = to.noser_split(crate::Ptr::SIZE as crate::Ptr)?;
+        crate::Ptr::write(footprint, size);
+
+        let (_, to) = E::copy_into(inner, to)?;
+        Ok((rest, to))
+    }
+}
+
+/// A switched variant is recorded byte for byte, a changed footprint as a whole.
+#[cfg(feature = "alloc")]
+impl<'a, E> Diff for DynamicEnumWrapper<'a, E>
+where
+    E: DynamicEnum<'a> + Diff,
+{
+    #[inline]
+    fn diff<'o, 'n>(
+        old: &'o [u8],
+        new: &'n [u8],
+        patch: &mut Patch,
+    ) -> crate::Result<(&'o [u8], &'n [u8])> {
+        let (old_footprint, new_footprint) =
+            (crate::Ptr::read_safe(old)?, crate::Ptr::read_safe(new)?);
+
+        if old_footprint != new_footprint {
+            let (old_size, new_size) = (Self::in_bounds(old)?, Self::in_bounds(new)?);
+            return patch.record(old, old_size, new, new_size);
+        }
+
+        let size = crate::Ptr::SIZE as crate::Ptr;
+        let (old, new) = patch.record(old, size, new, size)?;
+        variant_diff::<E>(old, new, old_footprint, E::VARIANT_BYTES, patch)
+    }
+}
+
+/// Only the current variant is compared and hashed, footprints may differ.
+impl<'a, E> Structural for DynamicEnumWrapper<'a, E>
+where
+    E: DynamicEnum<'a> + Structural,
+{
+    #[inline]
+    fn compare<'l, 'r>(
+        a: &'l [u8],
+        b: &'r [u8],
+        floats: Floats,
+    ) -> crate::Result<(Option<Ordering>, &'l [u8], &'r [u8])> {
+        let (footprint, a) = a.noser_split_imut(crate::Ptr::SIZE as crate::Ptr)?;
+        let (a, rest_a) = a.noser_split_imut(crate::Ptr::read(footprint))?;
+        let (footprint, b) = b.noser_split_imut(crate::Ptr::SIZE as crate::Ptr)?;
+        let (b, rest_b) = b.noser_split_imut(crate::Ptr::read(footprint))?;
+
+        E::compare(a, b, floats).map(|(ordering, _, _)| (ordering, rest_a, rest_b))
+    }
+
+    #[inline]
+    fn hash<'l, H: Hasher>(arena: &'l [u8], state: &mut H) -> crate::Result<&'l [u8]> {
+        let (footprint, arena) = arena.noser_split_imut(crate::Ptr::SIZE as crate::Ptr)?;
+        let (left, right) = arena.noser_split_imut(crate::Ptr::read(footprint))?;
+
+        E::hash(left, state)?;
+        Ok(right)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E> Json for DynamicEnumWrapper<'a, E>
+where
+    E: DynamicEnum<'a> + Json,
+{
+    #[inline]
+    fn to_json(arena: &[u8]) -> crate::Result<(Value, &[u8])> {
+        let (footprint, arena) = arena.noser_split_imut(crate::Ptr::SIZE as crate::Ptr)?;
+        let (left, right) = arena.noser_split_imut(crate::Ptr::read(footprint))?;
+
+        E::to_json(left).map(|(value, _)| (value, right))
+    }
+
+    #[inline]
+    fn json_size(value: &Value) -> crate::Result<crate::Ptr> {
+        E::json_size(value)?
+            .checked_add(crate::Ptr::SIZE as crate::Ptr)
+            .ok_or(crate::NoserError::IntegerOverflow)
+    }
+
+    #[inline]
+    fn imprint_json<'n>(value: &Value, arena: &'n mut [u8]) -> crate::Result<&'n mut [u8]> {
+        let (footprint, arena) = arena.noser_split(crate::Ptr::SIZE as crate::Ptr)?;
+        let size = E::json_size(value)?;
+
+        crate::Ptr::write(footprint, size);
+        E::imprint_json(value, arena)
+    }
+}
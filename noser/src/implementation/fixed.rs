@@ -0,0 +1,289 @@
+//! Fixed-point numbers, laid out like the integer holding their bits. Unlike floats their
+//! arithmetic gives the same result on every platform, which lockstep simulations rely on.
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+#[cfg(feature = "alloc")]
+use crate::json::{JsonError, Value};
+#[cfg(feature = "alloc")]
+use crate::traits::JsonLiteral;
+use crate::traits::{Floats, LiteralInnerType, Read, StructuralLiteral, Write};
+
+/// A number with `FRAC` fractional bits stored in the integer `I`, e.g. `Fixed<i32, 16>` is the
+/// common 16.16 format. `FRAC` has to be smaller than the number of bits of `I`.
+///
+/// Products and quotients are computed in an integer twice as wide. Products are rounded down,
+/// quotients towards zero. The operators panic on overflow, in debug and release builds alike, the
+/// `checked_*` methods return `None` instead.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed<I, const FRAC: u32> {
+    bits: I,
+}
+
+macro_rules! fixed {
+    ($($ty:ident $wide:ident),*) => {
+        $(
+            impl<const FRAC: u32> Fixed<$ty, FRAC> {
+                const FRAC_FITS: () = assert!(FRAC < $ty::BITS, "FRAC must be smaller than the bits of I");
+
+                /// The number `bits / 2^FRAC`.
+                #[inline]
+                pub const fn from_bits(bits: $ty) -> Self {
+                    #[allow(clippy::let_unit_value)]
+                    let () = Self::FRAC_FITS;
+                    Fixed { bits }
+                }
+
+                #[inline]
+                pub const fn to_bits(self) -> $ty {
+                    self.bits
+                }
+
+                /// None if `int` is out of range.
+                #[inline]
+                pub fn from_int(int: $ty) -> Option<Self> {
+                    $ty::try_from($wide::from(int) << FRAC).ok().map(Self::from_bits)
+                }
+
+                /// The integer part, rounded down.
+                #[inline]
+                pub fn to_int(self) -> $ty {
+                    self.bits >> FRAC
+                }
+
+                /// The nearest number to `f`, rounding halfway cases away from zero. None if `f`
+                /// is not finite or out of range.
+                #[inline]
+                pub fn from_f64(f: f64) -> Option<Self> {
+                    let scaled = f * ((1 as $wide) << FRAC) as f64;
+                    if !scaled.is_finite() {
+                        return None;
+                    }
+
+                    let rounded = if scaled < 0.0 { scaled - 0.5 } else { scaled + 0.5 };
+                    // The cast saturates, unsigned types would take negative numbers as 0.
+                    if rounded <= -1.0 && $ty::MIN == 0 {
+                        return None;
+                    }
+
+                    $ty::try_from(rounded as $wide).ok().map(Self::from_bits)
+                }
+
+                #[inline]
+                pub fn to_f64(self) -> f64 {
+                    self.bits as f64 / ((1 as $wide) << FRAC) as f64
+                }
+
+                #[inline]
+                pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                    self.bits.checked_add(rhs.bits).map(Self::from_bits)
+                }
+
+                #[inline]
+                pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    self.bits.checked_sub(rhs.bits).map(Self::from_bits)
+                }
+
+                #[inline]
+                pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    let product = $wide::from(self.bits) * $wide::from(rhs.bits);
+                    $ty::try_from(product >> FRAC).ok().map(Self::from_bits)
+                }
+
+                /// None if `rhs` is zero or the quotient is out of range.
+                #[inline]
+                pub fn checked_div(self, rhs: Self) -> Option<Self> {
+                    let dividend = $wide::from(self.bits) << FRAC;
+                    let quotient = dividend.checked_div($wide::from(rhs.bits))?;
+                    $ty::try_from(quotient).ok().map(Self::from_bits)
+                }
+            }
+
+            impl<const FRAC: u32> Add for Fixed<$ty, FRAC> {
+                type Output = Self;
+
+                #[inline]
+                fn add(self, rhs: Self) -> Self {
+                    self.checked_add(rhs).expect("fixed-point addition overflowed")
+                }
+            }
+
+            impl<const FRAC: u32> Sub for Fixed<$ty, FRAC> {
+                type Output = Self;
+
+                #[inline]
+                fn sub(self, rhs: Self) -> Self {
+                    self.checked_sub(rhs).expect("fixed-point subtraction overflowed")
+                }
+            }
+
+            impl<const FRAC: u32> Mul for Fixed<$ty, FRAC> {
+                type Output = Self;
+
+                #[inline]
+                fn mul(self, rhs: Self) -> Self {
+                    self.checked_mul(rhs).expect("fixed-point multiplication overflowed")
+                }
+            }
+
+            impl<const FRAC: u32> Div for Fixed<$ty, FRAC> {
+                type Output = Self;
+
+                #[inline]
+                fn div(self, rhs: Self) -> Self {
+                    self.checked_div(rhs).expect("fixed-point division by zero or overflowed")
+                }
+            }
+
+            impl<const FRAC: u32> fmt::Display for Fixed<$ty, FRAC> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.to_f64(), f)
+                }
+            }
+
+            impl<const FRAC: u32> fmt::Debug for Fixed<$ty, FRAC> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "Fixed({:?})", self.to_f64())
+                }
+            }
+
+            impl<const FRAC: u32> LiteralInnerType for Fixed<$ty, FRAC> {
+                const SIZE: usize = $ty::SIZE;
+
+                #[inline]
+                fn imprint(arena: &mut [u8]) -> crate::Result<()> {
+                    $ty::imprint(arena)
+                }
+            }
+
+            impl<const FRAC: u32> Write for Fixed<$ty, FRAC> {
+                #[inline]
+                fn write(arena: &mut [u8], val: Self) {
+                    $ty::write(arena, val.bits)
+                }
+            }
+
+            impl<const FRAC: u32> Read for Fixed<$ty, FRAC> {
+                type Output = Self;
+
+                #[inline]
+                fn read(arena: &[u8]) -> Self {
+                    Self::from_bits($ty::read(arena))
+                }
+            }
+
+            impl<const FRAC: u32> StructuralLiteral for Fixed<$ty, FRAC> {
+                #[inline]
+                fn compare(a: Self, b: Self, _: Floats) -> Option<Ordering> {
+                    Some(a.cmp(&b))
+                }
+            }
+
+            /// Written as a float, which is exact unless `I` has more than 53 significant bits.
+            #[cfg(feature = "alloc")]
+            impl<const FRAC: u32> JsonLiteral for Fixed<$ty, FRAC> {
+                #[inline]
+                fn to_value(val: Self) -> Value {
+                    Value::Float(val.to_f64())
+                }
+
+                #[inline]
+                fn from_value(value: &Value) -> crate::Result<Self> {
+                    let fixed = match value {
+                        Value::Float(f) => Self::from_f64(*f),
+                        Value::Int(i) => $ty::try_from(*i).ok().and_then(Self::from_int),
+                        Value::UInt(u) => $ty::try_from(*u).ok().and_then(Self::from_int),
+                        _ => return Err(JsonError::Mismatch("number").into()),
+                    };
+
+                    fixed.ok_or_else(|| JsonError::OutOfRange.into())
+                }
+            }
+        )*
+    };
+}
+
+fixed!(i8 i16, i16 i32, i32 i64, i64 i128, u8 u16, u16 u32, u32 u64, u64 u128);
+
+macro_rules! signed_fixed {
+    ($($ty:ident)*) => {
+        $(
+            impl<const FRAC: u32> Neg for Fixed<$ty, FRAC> {
+                type Output = Self;
+
+                #[inline]
+                fn neg(self) -> Self {
+                    let bits = self.bits.checked_neg().expect("fixed-point negation overflowed");
+                    Self::from_bits(bits)
+                }
+            }
+        )*
+    };
+}
+
+signed_fixed!(i8 i16 i32 i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type F16 = Fixed<i32, 16>;
+
+    fn f16(f: f64) -> F16 {
+        F16::from_f64(f).unwrap()
+    }
+
+    #[test]
+    fn rw_fixed() {
+        let arena = &mut [0; 4];
+
+        F16::write(arena, f16(1.5));
+        assert_eq!(arena, &[0, 0x80, 1, 0]);
+        assert_eq!(F16::read(arena), f16(1.5));
+    }
+
+    #[test]
+    fn fixed_arithmetic() {
+        assert_eq!(f16(1.5) + f16(2.25), f16(3.75));
+        assert_eq!(f16(1.5) - f16(2.25), f16(-0.75));
+        assert_eq!(f16(1.5) * f16(-2.5), f16(-3.75));
+        assert_eq!(f16(7.0) / f16(2.0), f16(3.5));
+        assert_eq!(-f16(0.5), f16(-0.5));
+        assert_eq!(f16(-0.5).to_int(), -1);
+        assert_eq!(F16::from_int(3), Some(f16(3.0)));
+
+        assert_eq!(F16::from_int(1 << 15), None);
+        assert_eq!(f16(1.0).checked_div(f16(0.0)), None);
+        assert_eq!(f16(30000.0).checked_mul(f16(2.0)), None);
+        assert_eq!(Fixed::<u8, 4>::from_bits(0).to_bits(), 0);
+    }
+
+    #[test]
+    fn fixed_rounding() {
+        let third = f16(1.0) / f16(3.0);
+
+        assert_eq!(third.to_bits(), 0x5555);
+        assert_eq!((-f16(1.0) / f16(3.0)).to_bits(), -0x5555);
+        assert_eq!(F16::from_f64(0.5 / 65536.0).unwrap().to_bits(), 1);
+        assert_eq!(F16::from_f64(-0.5 / 65536.0).unwrap().to_bits(), -1);
+        assert_eq!(F16::from_f64(f64::NAN), None);
+        assert_eq!(F16::from_f64(32768.0), None);
+
+        assert_eq!(Fixed::<u8, 4>::from_f64(-5.0), None);
+        assert_eq!(Fixed::<u8, 4>::from_f64(-0.01).unwrap().to_bits(), 0);
+        assert_eq!(Fixed::<u64, 8>::from_f64(-1.0), None);
+    }
+
+    #[test]
+    fn json_fixed() {
+        assert_eq!(F16::to_value(f16(-2.75)), Value::Float(-2.75));
+        assert_eq!(F16::from_value(&Value::Float(-2.75)).unwrap(), f16(-2.75));
+        assert_eq!(F16::from_value(&Value::UInt(4)).unwrap(), f16(4.0));
+        assert!(F16::from_value(&Value::Int(1 << 20)).is_err());
+        assert!(F16::from_value(&Value::Null).is_err());
+        assert!(Fixed::<u16, 4>::from_value(&Value::Float(-3.0)).is_err());
+        assert!(Fixed::<u16, 4>::from_value(&Value::Int(-3)).is_err());
+    }
+}
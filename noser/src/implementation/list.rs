@@ -1,11 +1,22 @@
+#[cfg(feature = "alloc")]
+use crate::diff::Patch;
+#[cfg(feature = "alloc")]
+use crate::json::Value;
 use crate::prelude::SliceExt;
 use crate::traits::{
     size::{Dynamic, SizeKind, Sizeable},
-    Build, DefaultWriter, LiteralInnerType, Read, WriteTypeInfo,
+    Build, CopyInto, DefaultWriter, Floats, LiteralInnerType, Read, Reborrow, Structural, Write,
+    WriteTypeInfo,
 };
+#[cfg(feature = "alloc")]
+use crate::traits::{Diff, Json};
 use crate::writer::list::{FromSlice, WithCapacity};
 
-use std::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::hash::Hasher;
+use core::marker::PhantomData;
 
 // We cannot have a method &mut self -> T on List as &mut Self is invariant on Self.
 // As such T's lifetime cannot be narrowed. We also cannot have a method
@@ -221,6 +232,191 @@ where
     }
 }
 
+impl<'a, T> Reborrow<'a> for List<'static, T>
+where
+    T: Reborrow<'a>,
+    T::View: Sizeable,
+{
+    type View = List<'a, T::View>;
+}
+
+impl<T> CopyInto for List<'_, T>
+where
+    T: CopyInto,
+{
+    #[inline]
+    fn copy_size(from: &[u8]) -> crate::Result<(crate::Ptr, &[u8])> {
+        let capacity = ListLen::read_safe(from)?;
+        let mut from = &from[ListLen::SIZE..];
+        let mut size = ListLen::SIZE as crate::Ptr;
+
+        for _ in 0..capacity {
+            let (item, rest) = T::copy_size(from)?;
+            size = size
+                .checked_add(item)
+                .ok_or(crate::NoserError::IntegerOverflow)?;
+            from = rest;
+        }
+
+        Ok((size, from))
+    }
+
+    #[inline]
+    fn copy_into<'f, 't>(
+        from: &'f [u8],
+        to: &'t mut [u8],
+    ) -> crate::Result<(&'f [u8], &'t mut [u8])> {
+        let capacity = ListLen::read_safe(from)?;
+        let (len, mut to) = to.noser_split(ListLen::SIZE as crate::Ptr)?;
+        let mut from = &from[ListLen::SIZE..];
+
+        ListLen::write(len, capacity);
+        for _ in 0..capacity {
+            let (rest_from, rest_to) = T::copy_into(from, to)?;
+            from = rest_from;
+            to = rest_to;
+        }
+
+        Ok((from, to))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Diff for List<'_, T>
+where
+    T: Diff,
+{
+    #[inline]
+    fn diff<'o, 'n>(
+        old: &'o [u8],
+        new: &'n [u8],
+        patch: &mut Patch,
+    ) -> crate::Result<(&'o [u8], &'n [u8])> {
+        let (old_len, new_len) = (ListLen::read_safe(old)?, ListLen::read_safe(new)?);
+        let size = ListLen::SIZE as crate::Ptr;
+        let (mut old, mut new) = patch.record(old, size, new, size)?;
+
+        let common = old_len.min(new_len);
+        for _ in 0..common {
+            let (old_rest, new_rest) = T::diff(old, new, patch)?;
+            old = old_rest;
+            new = new_rest;
+        }
+
+        // Items only one of the lists has are removed or inserted at its end.
+        let old_size = items_size::<T>(old, old_len - common)?;
+        let new_size = items_size::<T>(new, new_len - common)?;
+        patch.record(old, old_size, new, new_size)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+fn items_size<T: Sizeable>(arena: &[u8], count: ListLen) -> crate::Result<crate::Ptr> {
+    let mut size: crate::Ptr = 0;
+
+    for _ in 0..count {
+        size = size
+            .checked_add(T::in_bounds(&arena[size as usize..])?)
+            .ok_or(crate::NoserError::IntegerOverflow)?;
+    }
+
+    Ok(size)
+}
+
+impl<T> Structural for List<'_, T>
+where
+    T: Structural,
+{
+    #[inline]
+    fn compare<'a, 'b>(
+        a: &'a [u8],
+        b: &'b [u8],
+        floats: Floats,
+    ) -> crate::Result<(Option<Ordering>, &'a [u8], &'b [u8])> {
+        let (len_a, len_b) = (ListLen::read_safe(a)?, ListLen::read_safe(b)?);
+        let (mut a, mut b) = (&a[ListLen::SIZE..], &b[ListLen::SIZE..]);
+
+        for _ in 0..len_a.min(len_b) {
+            let (ordering, rest_a, rest_b) = T::compare(a, b, floats)?;
+            if ordering != Some(Ordering::Equal) {
+                return Ok((ordering, rest_a, rest_b));
+            }
+
+            a = rest_a;
+            b = rest_b;
+        }
+
+        Ok((Some(len_a.cmp(&len_b)), a, b))
+    }
+
+    #[inline]
+    fn hash<'a, H: Hasher>(arena: &'a [u8], state: &mut H) -> crate::Result<&'a [u8]> {
+        let len = ListLen::read_safe(arena)?;
+        let mut arena = &arena[ListLen::SIZE..];
+
+        state.write(&len.to_le_bytes());
+        for _ in 0..len {
+            arena = T::hash(arena, state)?;
+        }
+
+        Ok(arena)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Json for List<'_, T>
+where
+    T: Json,
+{
+    fn to_json(arena: &[u8]) -> crate::Result<(Value, &[u8])> {
+        let (left, mut arena) = arena.noser_split_imut(ListLen::SIZE as crate::Ptr)?;
+        let capacity = ListLen::read(left);
+
+        let mut items = Vec::with_capacity(capacity.min(arena.len() as ListLen) as usize);
+
+        for _ in 0..capacity {
+            let (item, right) = T::to_json(arena)?;
+            items.push(item);
+            arena = right;
+        }
+
+        Ok((Value::Array(items), arena))
+    }
+
+    fn json_size(value: &Value) -> crate::Result<crate::Ptr> {
+        let items = value.as_array()?;
+
+        if items.len() > ListLen::MAX as usize {
+            return Err(crate::NoserError::IntegerOverflow);
+        }
+
+        items
+            .iter()
+            .try_fold(ListLen::SIZE as crate::Ptr, |size, item| {
+                size.checked_add(T::json_size(item)?)
+                    .ok_or(crate::NoserError::IntegerOverflow)
+            })
+    }
+
+    fn imprint_json<'n>(value: &Value, arena: &'n mut [u8]) -> crate::Result<&'n mut [u8]> {
+        let items = value.as_array()?;
+
+        if items.len() > ListLen::MAX as usize {
+            return Err(crate::NoserError::IntegerOverflow);
+        }
+
+        let (left, mut arena) = arena.noser_split(ListLen::SIZE as crate::Ptr)?;
+        ListLen::write(left, items.len() as ListLen);
+
+        for item in items {
+            arena = T::imprint_json(item, arena)?;
+        }
+
+        Ok(arena)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,4 +534,19 @@ mod tests {
         let owned = List::<Literal<'_, u8>>::create(&mut arena).unwrap();
         owned.borrow(49);
     }
+
+    #[test]
+    fn json_nested_list() {
+        let value = Value::parse("[[1,2],[],[3]]").unwrap();
+        let mut arena = crate::json::from_json::<List<List<Literal<u8>>>>(&value).unwrap();
+
+        {
+            let owned = List::<List<'_, Literal<'_, u8>>>::create(&mut arena).unwrap();
+            assert_eq!(owned.capacity(), 3);
+            assert_eq!(owned.borrow(2).borrow(0).read(), 3);
+        }
+
+        let exported = crate::json::to_json::<List<List<Literal<u8>>>>(&arena).unwrap();
+        assert_eq!(exported.to_string(), "[[1,2],[],[3]]");
+    }
 }
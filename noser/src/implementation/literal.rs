@@ -1,10 +1,20 @@
+#[cfg(feature = "alloc")]
+use crate::diff::Patch;
+#[cfg(feature = "alloc")]
+use crate::json::Value;
 use crate::prelude::SliceExt;
 use crate::traits::{
+    raw_copy_into, raw_copy_size,
     size::{ReadReturn, Sizeable, Static},
-    Build, LiteralInnerType, Read, Write,
+    Build, CopyInto, Floats, LiteralInnerType, Read, Reborrow, Structural, StructuralLiteral,
+    Write,
 };
+#[cfg(feature = "alloc")]
+use crate::traits::{raw_diff, Diff, Json, JsonLiteral};
 
-use std::marker::PhantomData;
+use core::cmp::Ordering;
+use core::hash::Hasher;
+use core::marker::PhantomData;
 
 #[derive(Debug)]
 pub struct Literal<'l, T> {
@@ -31,7 +41,13 @@ where
 {
     #[inline]
     pub fn write(&mut self, val: T) {
-        T::write(self.arena, val)
+        #[cfg(feature = "transaction")]
+        crate::transaction::journal(self.arena);
+
+        T::write(self.arena, val);
+
+        #[cfg(feature = "dirty")]
+        crate::dirty::mark(self.arena);
     }
 }
 
@@ -72,6 +88,7 @@ where
         'n: 'l,
     {
         let (left, right) = arena.noser_split(T::SIZE as crate::Ptr)?;
+        T::validate(left)?;
 
         Ok((
             right,
@@ -83,6 +100,103 @@ where
     }
 }
 
+impl<'a, T> Reborrow<'a> for Literal<'static, T>
+where
+    T: LiteralInnerType,
+{
+    type View = Literal<'a, T>;
+}
+
+impl<T> CopyInto for Literal<'_, T>
+where
+    T: LiteralInnerType,
+{
+    #[inline]
+    fn copy_size(from: &[u8]) -> crate::Result<(crate::Ptr, &[u8])> {
+        raw_copy_size::<Self>(from)
+    }
+
+    #[inline]
+    fn copy_into<'f, 't>(
+        from: &'f [u8],
+        to: &'t mut [u8],
+    ) -> crate::Result<(&'f [u8], &'t mut [u8])> {
+        raw_copy_into::<Self>(from, to)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Diff for Literal<'_, T>
+where
+    T: LiteralInnerType,
+{
+    #[inline]
+    fn diff<'o, 'n>(
+        old: &'o [u8],
+        new: &'n [u8],
+        patch: &mut Patch,
+    ) -> crate::Result<(&'o [u8], &'n [u8])> {
+        raw_diff::<Self>(old, new, patch)
+    }
+}
+
+impl<T> Structural for Literal<'_, T>
+where
+    T: LiteralInnerType + StructuralLiteral,
+{
+    #[inline]
+    fn compare<'a, 'b>(
+        a: &'a [u8],
+        b: &'b [u8],
+        floats: Floats,
+    ) -> crate::Result<(Option<Ordering>, &'a [u8], &'b [u8])> {
+        let (a, rest_a) = a.noser_split_imut(T::SIZE as crate::Ptr)?;
+        let (b, rest_b) = b.noser_split_imut(T::SIZE as crate::Ptr)?;
+        T::validate(a)?;
+        T::validate(b)?;
+
+        Ok((T::compare(T::read(a), T::read(b), floats), rest_a, rest_b))
+    }
+
+    #[inline]
+    fn hash<'a, H: Hasher>(arena: &'a [u8], state: &mut H) -> crate::Result<&'a [u8]> {
+        let (left, right) = arena.noser_split_imut(T::SIZE as crate::Ptr)?;
+        T::validate(left)?;
+
+        state.write(left);
+        Ok(right)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Json for Literal<'_, T>
+where
+    T: JsonLiteral,
+{
+    #[inline]
+    fn to_json(arena: &[u8]) -> crate::Result<(Value, &[u8])> {
+        let (left, right) = arena.noser_split_imut(T::SIZE as crate::Ptr)?;
+        T::validate(left)?;
+
+        Ok((T::to_value(T::read(left)), right))
+    }
+
+    #[inline]
+    fn json_size(value: &Value) -> crate::Result<crate::Ptr> {
+        T::from_value(value)?;
+        Ok(T::SIZE as crate::Ptr)
+    }
+
+    #[inline]
+    fn imprint_json<'n>(value: &Value, arena: &'n mut [u8]) -> crate::Result<&'n mut [u8]> {
+        let (left, right) = arena.noser_split(T::SIZE as crate::Ptr)?;
+
+        T::imprint(left)?;
+        T::write(left, T::from_value(value)?);
+        Ok(right)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,4 +226,42 @@ mod tests {
         println!("{:?}", results);
         assert!(results.into_iter().all(|r| r.is_err()));
     }
+
+    #[test]
+    fn json() {
+        let value = Value::Int(-1234);
+        let arena = crate::json::from_json::<Literal<i16>>(&value).unwrap();
+
+        assert_eq!(crate::json::to_json::<Literal<i16>>(&arena).unwrap(), value);
+        assert!(crate::json::from_json::<Literal<u8>>(&Value::UInt(256)).is_err());
+    }
+
+    #[test]
+    fn non_canonical_literals() {
+        use crate::List;
+
+        for byte in 0..=255u8 {
+            let arena = &mut [byte];
+            let built = <Literal<bool>>::create(arena).map(|b| b.read());
+            let mut out = false;
+            let read = unsafe { crate::ffi::read::<bool>(Ok(arena), &mut out) };
+            assert_eq!(read, byte <= 1);
+
+            match byte {
+                0 | 1 => assert_eq!(built.unwrap(), byte == 1),
+                _ => {
+                    assert!(built.is_err());
+                    assert!(crate::json::to_json::<Literal<bool>>(arena).is_err());
+                    assert!(<Literal<bool>>::equal(arena, arena, Floats::Total).is_err());
+                }
+            }
+        }
+
+        let mut arena = vec![2, 0, 0, 0, 0x41, 0, 0, 0, 0, 0xd8, 0, 0];
+        assert!(<List<Literal<char>>>::create(&mut arena).is_err());
+
+        arena[9] = 0xd7;
+        let chars = <List<Literal<char>>>::create(&mut arena).unwrap();
+        assert_eq!(chars.borrow(1).read(), Some('\u{d700}'));
+    }
 }
@@ -3,9 +3,14 @@ mod literal;
 mod scalars;
 mod slice;
 mod enum_wrapper;
+mod duration;
+mod fixed;
+mod uuid;
 
 pub use self::list::*;
 pub use self::literal::*;
 pub use self::scalars::*;
 pub(crate) use self::slice::*;
 pub use self::enum_wrapper::*;
+pub use self::fixed::*;
+pub use self::uuid::*;
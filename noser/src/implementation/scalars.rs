@@ -1,9 +1,22 @@
-use std::mem;
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+use core::mem;
 
-use crate::traits::{LiteralInnerType, Read, Write};
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+
+#[cfg(feature = "alloc")]
+use crate::json::{JsonError, Value};
+#[cfg(feature = "alloc")]
+use crate::traits::JsonLiteral;
+use crate::traits::{Floats, LiteralInnerType, Read, StructuralLiteral, Write};
 
 macro_rules! impl_rw {
     ($ty:ident, $($rw:tt)*) => {
+        impl_rw!($ty where |_arena| true, $($rw)*);
+    };
+    ($ty:ident where |$arena:ident| $valid:expr, $($rw:tt)*) => {
         $($rw)*
 
         impl LiteralInnerType for $ty {
@@ -15,7 +28,16 @@ macro_rules! impl_rw {
                     // Scalars don't need to write any size information
                     Ok(())
                 } else {
-                    Err(crate::NoserError::Undersized(mem::size_of::<$ty>(), arena.to_vec()))
+                    Err(crate::NoserError::undersized(mem::size_of::<$ty>(), arena))
+                }
+            }
+
+            #[inline]
+            fn validate($arena: &[u8]) -> crate::Result<()> {
+                if $valid {
+                    Ok(())
+                } else {
+                    Err(crate::NoserError::Malformed)
                 }
             }
         }
@@ -30,7 +52,7 @@ macro_rules! transmutable_without_endianness_transform {
                 fn write(arena: &mut [u8], val: $ty) {
                     #[allow(clippy::cast_ptr_alignment)]
                     let mut_ptr = (&mut arena[..mem::size_of::<$ty>()]).as_mut_ptr() as *mut $ty;
-                    unsafe { std::ptr::write_unaligned(mut_ptr, val) }
+                    unsafe { core::ptr::write_unaligned(mut_ptr, val) }
                 }
             }
 
@@ -41,7 +63,7 @@ macro_rules! transmutable_without_endianness_transform {
                 fn read(arena: &[u8]) -> $ty {
                     #[allow(clippy::cast_ptr_alignment)]
                     let p = (&arena[..Self::SIZE]).as_ptr() as *const $ty;
-                    unsafe { std::ptr::read_unaligned(p) }
+                    unsafe { core::ptr::read_unaligned(p) }
                 }
             }
         );
@@ -56,7 +78,7 @@ macro_rules! transmutable {
                 fn write(arena: &mut [u8], val: $ty) {
                     #[allow(clippy::cast_ptr_alignment)]
                     let mut_ptr = (&mut arena[..mem::size_of::<$ty>()]).as_mut_ptr() as *mut $ty;
-                    unsafe { std::ptr::write_unaligned(mut_ptr, val.to_le()) }
+                    unsafe { core::ptr::write_unaligned(mut_ptr, val.to_le()) }
                 }
             }
 
@@ -67,7 +89,7 @@ macro_rules! transmutable {
                 fn read(arena: &[u8]) -> $ty {
                     #[allow(clippy::cast_ptr_alignment)]
                     let p = (&arena[..mem::size_of::<$ty>()]).as_ptr() as *const $ty;
-                    $ty::from_le(unsafe { std::ptr::read_unaligned(p) })
+                    $ty::from_le(unsafe { core::ptr::read_unaligned(p) })
                 }
             }
         );
@@ -92,7 +114,6 @@ impl_rw!(u8,
     }
 );
 
-transmutable_without_endianness_transform!(bool);
 transmutable_without_endianness_transform!(i8);
 
 transmutable!(i16);
@@ -106,7 +127,26 @@ transmutable!(i128);
 #[cfg(feature = "u128")]
 transmutable!(u128);
 
-impl_rw!(char,
+// Bools are not transmuted, any byte but 0 and 1 would be an invalid bool.
+impl_rw!(bool where |arena| arena[0] <= 1,
+    impl Write for bool {
+        #[inline]
+        fn write(arena: &mut [u8], val: bool) {
+            arena[0] = val as u8
+        }
+    }
+
+    impl Read for bool {
+        type Output = bool;
+
+        #[inline]
+        fn read(arena: &[u8]) -> bool {
+            arena[0] != 0
+        }
+    }
+);
+
+impl_rw!(char where |arena| ::core::char::from_u32(u32::read(arena)).is_some(),
     impl Write for char {
         #[inline]
         fn write(arena: &mut [u8], val: char) {
@@ -119,7 +159,7 @@ impl_rw!(char,
 
         #[inline]
         fn read(arena: &[u8]) -> Option<char> {
-            ::std::char::from_u32(u32::read(arena))
+            ::core::char::from_u32(u32::read(arena))
         }
     }
 );
@@ -160,6 +200,171 @@ impl_rw!(f64,
     }
 );
 
+#[cfg(feature = "alloc")]
+macro_rules! json_integer {
+    ($variant:ident, $as:ty, $($ty:ident)*) => {
+        $(
+            impl JsonLiteral for $ty {
+                #[inline]
+                fn to_value(val: $ty) -> Value {
+                    Value::$variant(val as $as)
+                }
+
+                #[inline]
+                fn from_value(value: &Value) -> crate::Result<$ty> {
+                    match value {
+                        Value::Int(i) => $ty::try_from(*i).map_err(|_| JsonError::OutOfRange.into()),
+                        Value::UInt(u) => $ty::try_from(*u).map_err(|_| JsonError::OutOfRange.into()),
+                        _ => Err(JsonError::Mismatch("integer").into()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "alloc")]
+json_integer!(UInt, u64, u8 u16 u32 u64);
+#[cfg(feature = "alloc")]
+json_integer!(Int, i64, i8 i16 i32 i64);
+
+/// Json numbers are read as 64 bit integers, so larger values are written as decimal strings.
+#[cfg(all(feature = "alloc", any(feature = "i128", feature = "u128")))]
+macro_rules! json_wide_integer {
+    ($variant:ident, $as:ty, $ty:ident) => {
+        impl JsonLiteral for $ty {
+            #[inline]
+            fn to_value(val: $ty) -> Value {
+                match <$as>::try_from(val) {
+                    Ok(val) => Value::$variant(val),
+                    Err(_) => Value::String(val.to_string()),
+                }
+            }
+
+            #[inline]
+            fn from_value(value: &Value) -> crate::Result<$ty> {
+                match value {
+                    Value::Int(i) => $ty::try_from(*i).map_err(|_| JsonError::OutOfRange.into()),
+                    Value::UInt(u) => $ty::try_from(*u).map_err(|_| JsonError::OutOfRange.into()),
+                    Value::String(s) => {
+                        s.parse().map_err(|_| JsonError::Mismatch("integer").into())
+                    }
+                    _ => Err(JsonError::Mismatch("integer").into()),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "alloc", feature = "u128"))]
+json_wide_integer!(UInt, u64, u128);
+#[cfg(all(feature = "alloc", feature = "i128"))]
+json_wide_integer!(Int, i64, i128);
+
+#[cfg(feature = "alloc")]
+impl JsonLiteral for bool {
+    #[inline]
+    fn to_value(val: bool) -> Value {
+        Value::Bool(val)
+    }
+
+    #[inline]
+    fn from_value(value: &Value) -> crate::Result<bool> {
+        match value {
+            Value::Bool(b) => Ok(*b),
+            _ => Err(JsonError::Mismatch("bool").into()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl JsonLiteral for char {
+    #[inline]
+    fn to_value(val: Option<char>) -> Value {
+        val.map(|c| Value::String(c.to_string()))
+            .unwrap_or(Value::Null)
+    }
+
+    #[inline]
+    fn from_value(value: &Value) -> crate::Result<char> {
+        let mut chars = match value {
+            Value::String(s) => s.chars(),
+            _ => return Err(JsonError::Mismatch("char").into()),
+        };
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(JsonError::Mismatch("char").into()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! json_float {
+    ($($ty:ident)*) => {
+        $(
+            impl JsonLiteral for $ty {
+                #[inline]
+                fn to_value(val: $ty) -> Value {
+                    Value::Float(f64::from(val))
+                }
+
+                /// Json can not represent NaN, as such null is read as NaN.
+                #[inline]
+                fn from_value(value: &Value) -> crate::Result<$ty> {
+                    match value {
+                        Value::Float(f) => Ok(*f as $ty),
+                        Value::Int(i) => Ok(*i as $ty),
+                        Value::UInt(u) => Ok(*u as $ty),
+                        Value::Null => Ok($ty::NAN),
+                        _ => Err(JsonError::Mismatch("number").into()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "alloc")]
+json_float!(f32 f64);
+
+macro_rules! structural_ord {
+    ($($ty:ident)*) => {
+        $(
+            impl StructuralLiteral for $ty {
+                #[inline]
+                fn compare(a: Self::Output, b: Self::Output, _: Floats) -> Option<Ordering> {
+                    Some(a.cmp(&b))
+                }
+            }
+        )*
+    };
+}
+
+structural_ord!(u8 u16 u32 u64 i8 i16 i32 i64 bool char);
+#[cfg(feature = "i128")]
+structural_ord!(i128);
+#[cfg(feature = "u128")]
+structural_ord!(u128);
+
+macro_rules! structural_float {
+    ($($ty:ident)*) => {
+        $(
+            impl StructuralLiteral for $ty {
+                #[inline]
+                fn compare(a: $ty, b: $ty, floats: Floats) -> Option<Ordering> {
+                    match floats {
+                        Floats::Ieee => a.partial_cmp(&b),
+                        Floats::Total => Some(a.total_cmp(&b)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+structural_float!(f32 f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -175,6 +380,28 @@ mod tests {
         assert!(!bool::read(arena));
     }
 
+    #[test]
+    fn validate_bool() {
+        for byte in 0..=255u8 {
+            assert_eq!(bool::validate(&[byte]).is_ok(), byte <= 1);
+            assert_eq!(bool::read(&[byte]), byte != 0);
+            assert_eq!(bool::read_safe(&[byte]).is_ok(), byte <= 1);
+        }
+    }
+
+    #[test]
+    fn validate_char() {
+        for byte in 0..=255u8 {
+            // Surrogates are 0xd800 to 0xdfff, the largest char is 0x10ffff.
+            let surrogate = (0xd8..=0xdf).contains(&byte);
+            assert_eq!(char::validate(&[0, byte, 0, 0]).is_ok(), !surrogate);
+            assert_eq!(char::validate(&[0, 0, byte, 0]).is_ok(), byte <= 0x10);
+            assert_eq!(char::validate(&[0, 0, 0, byte]).is_ok(), byte == 0);
+            assert_eq!(char::read(&[0, 0, 0, byte]).is_some(), byte == 0);
+            assert_eq!(char::read_safe(&[0, 0, byte, 0]).is_ok(), byte <= 0x10);
+        }
+    }
+
     #[test]
     fn rw_char() {
         let arena = &mut [0; 4];
@@ -207,6 +434,48 @@ mod tests {
         assert!(246 == u64::read(arena));
     }
 
+    #[test]
+    #[cfg(feature = "u128")]
+    fn rw_u128() {
+        let arena = &mut [0; 16];
+
+        u128::write(arena, u128::MAX - 246);
+        assert!(u128::MAX - 246 == u128::read(arena));
+        assert_eq!(arena[0], 9);
+    }
+
+    #[test]
+    #[cfg(feature = "i128")]
+    fn rw_i128() {
+        let arena = &mut [0; 16];
+
+        i128::write(arena, -246);
+        assert!(-246 == i128::read(arena));
+        assert_eq!(arena[15], 255);
+    }
+
+    #[test]
+    #[cfg(all(feature = "i128", feature = "u128"))]
+    fn json_wide_integers() {
+        assert_eq!(u128::to_value(7), Value::UInt(7));
+        assert_eq!(
+            u128::to_value(u128::MAX),
+            Value::String(u128::MAX.to_string())
+        );
+        assert_eq!(
+            i128::to_value(i128::MIN),
+            Value::String(i128::MIN.to_string())
+        );
+
+        assert_eq!(
+            u128::from_value(&u128::to_value(u128::MAX)).unwrap(),
+            u128::MAX
+        );
+        assert_eq!(i128::from_value(&Value::Int(-3)).unwrap(), -3);
+        assert!(u128::from_value(&Value::Int(-3)).is_err());
+        assert!(i128::from_value(&Value::String("x".to_string())).is_err());
+    }
+
     #[test]
     #[allow(clippy::float_cmp)]
     fn rw_f32() {
@@ -1,9 +1,21 @@
+use core::cmp::Ordering;
+use core::hash::Hasher;
+
+#[cfg(feature = "alloc")]
+use crate::diff::Patch;
+#[cfg(feature = "alloc")]
+use crate::json::Value;
 use crate::prelude::SliceExt;
-use crate::traits::{size::Dynamic, size::Sizeable, Build, LiteralInnerType, Read};
+use crate::traits::{
+    raw_copy_into, raw_copy_size, size::Dynamic, size::Sizeable, Build, CopyInto, Floats,
+    LiteralInnerType, Read, Reborrow, Structural,
+};
+#[cfg(feature = "alloc")]
+use crate::traits::{raw_diff, Diff, Json, JsonLiteral, Write};
 
 pub(crate) trait SliceType {
     type ElemType;
-    const ELEM_SIZE: usize = std::mem::size_of::<Self::ElemType>();
+    const ELEM_SIZE: usize = core::mem::size_of::<Self::ElemType>();
 }
 
 impl SliceType for &'_ [u8] {
@@ -49,7 +61,7 @@ macro_rules! build_slice {
                 'a: 'b,
             {
                 let len = crate::Ptr::read(arena);
-                let (this, right) = arena.split_at_mut(len as usize);
+                let (this, right) = arena.split_at_mut(crate::Ptr::SIZE + len as usize);
 
                 (right, &mut this[crate::Ptr::SIZE..])
             }
@@ -57,10 +69,125 @@ macro_rules! build_slice {
     };
 }
 
+#[cfg(feature = "alloc")]
+macro_rules! json_slice {
+    ($type:ty) => {
+        impl Json for $type {
+            fn to_json(arena: &[u8]) -> crate::Result<(Value, &[u8])> {
+                let (len_bytes, arena) = arena.noser_split_imut(crate::Ptr::SIZE as crate::Ptr)?;
+                let (this, right) = arena.noser_split_imut(crate::Ptr::read(len_bytes))?;
+
+                Ok((
+                    Value::Array(this.iter().map(|byte| u8::to_value(*byte)).collect()),
+                    right,
+                ))
+            }
+
+            fn json_size(value: &Value) -> crate::Result<crate::Ptr> {
+                let items = value.as_array()?;
+
+                for item in items {
+                    u8::from_value(item)?;
+                }
+
+                (items.len() as u64)
+                    .checked_mul(<$type>::ELEM_SIZE as u64)
+                    .map(|size| size + crate::Ptr::SIZE as u64)
+                    .filter(|size| *size <= u64::from(crate::Ptr::MAX))
+                    .map(|size| size as crate::Ptr)
+                    .ok_or(crate::NoserError::IntegerOverflow)
+            }
+
+            fn imprint_json<'n>(value: &Value, arena: &'n mut [u8]) -> crate::Result<&'n mut [u8]> {
+                let items = value.as_array()?;
+
+                let (len_bytes, arena) = arena.noser_split(crate::Ptr::SIZE as crate::Ptr)?;
+                let (this, right) = arena.noser_split(items.len() as crate::Ptr)?;
+
+                crate::Ptr::write(len_bytes, items.len() as crate::Ptr);
+                for (byte, item) in this.iter_mut().zip(items) {
+                    *byte = u8::from_value(item)?;
+                }
+
+                Ok(right)
+            }
+        }
+    };
+}
+
+macro_rules! copy_slice {
+    ($type:ty) => {
+        impl CopyInto for $type {
+            #[inline]
+            fn copy_size(from: &[u8]) -> crate::Result<(crate::Ptr, &[u8])> {
+                raw_copy_size::<Self>(from)
+            }
+
+            #[inline]
+            fn copy_into<'f, 't>(
+                from: &'f [u8],
+                to: &'t mut [u8],
+            ) -> crate::Result<(&'f [u8], &'t mut [u8])> {
+                raw_copy_into::<Self>(from, to)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl Diff for $type {
+            #[inline]
+            fn diff<'o, 'n>(
+                old: &'o [u8],
+                new: &'n [u8],
+                patch: &mut Patch,
+            ) -> crate::Result<(&'o [u8], &'n [u8])> {
+                raw_diff::<Self>(old, new, patch)
+            }
+        }
+
+        impl Structural for $type {
+            #[inline]
+            fn compare<'a, 'b>(
+                a: &'a [u8],
+                b: &'b [u8],
+                _: Floats,
+            ) -> crate::Result<(Option<Ordering>, &'a [u8], &'b [u8])> {
+                let (size_a, rest_a) = raw_copy_size::<Self>(a)?;
+                let (size_b, rest_b) = raw_copy_size::<Self>(b)?;
+
+                let a = &a[crate::Ptr::SIZE..size_a as usize];
+                let b = &b[crate::Ptr::SIZE..size_b as usize];
+                Ok((Some(a.cmp(b)), rest_a, rest_b))
+            }
+
+            #[inline]
+            fn hash<'a, H: Hasher>(arena: &'a [u8], state: &mut H) -> crate::Result<&'a [u8]> {
+                let (size, rest) = raw_copy_size::<Self>(arena)?;
+
+                state.write(&arena[..size as usize]);
+                Ok(rest)
+            }
+        }
+    };
+}
+
 slice_sizable! { &[u8] }
 slice_sizable! { &mut [u8] }
 build_slice! { 'b, &'b [u8] }
 build_slice! { 'b, &'b mut [u8] }
+#[cfg(feature = "alloc")]
+json_slice! { &[u8] }
+#[cfg(feature = "alloc")]
+json_slice! { &mut [u8] }
+copy_slice! { &[u8] }
+copy_slice! { &mut [u8] }
+
+impl<'a> Reborrow<'a> for &'static [u8] {
+    type View = &'a [u8];
+}
+
+impl<'a> Reborrow<'a> for &'static mut [u8] {
+    type View = &'a mut [u8];
+}
 
 #[cfg(test)]
 mod tests {
@@ -99,4 +226,28 @@ mod tests {
         let result = <&mut [u8]>::create(&mut arena).unwrap();
         assert!(Ok("こんにちは") == std::str::from_utf8(result));
     }
+
+    #[test]
+    fn unchecked_build_skips_the_length() {
+        // A byte slice followed by a `u16` field.
+        let mut arena = [3, 0, 0, 0, 1, 2, 3, 7, 0];
+
+        let (right, bytes) = <&mut [u8]>::unchecked_build(&mut arena);
+        assert_eq!(bytes, [1, 2, 3]);
+        assert_eq!(crate::Literal::<u16>::create(right).unwrap().read(), 7);
+
+        let (right, bytes) = <&mut [u8]>::build(&mut arena).unwrap();
+        assert_eq!(bytes, [1, 2, 3]);
+        assert_eq!(right, [7, 0]);
+    }
+
+    #[test]
+    fn json_byte_slice() {
+        let value = Value::parse("[1,2,255]").unwrap();
+        let arena = crate::json::from_json::<&[u8]>(&value).unwrap();
+
+        assert_eq!(arena, vec![3, 0, 0, 0, 1, 2, 255]);
+        assert_eq!(crate::json::to_json::<&[u8]>(&arena).unwrap(), value);
+        assert!(crate::json::from_json::<&[u8]>(&Value::parse("[256]").unwrap()).is_err());
+    }
 }
@@ -0,0 +1,200 @@
+//! A 16 byte id, stored as is so it reads back the same on every platform.
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+
+#[cfg(feature = "alloc")]
+use crate::json::{JsonError, Value};
+#[cfg(feature = "alloc")]
+use crate::traits::JsonLiteral;
+use crate::traits::{Floats, LiteralInnerType, Read, StructuralLiteral, Write};
+
+/// An UUID or any other 128 bit id. The bytes are kept in the order they are printed in, so ids
+/// compare like their hyphenated strings.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Uuid(pub [u8; 16]);
+
+impl Uuid {
+    pub const NIL: Uuid = Uuid([0; 16]);
+
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Uuid(bytes)
+    }
+
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// The most significant byte of `value` becomes the first byte of the id.
+    #[inline]
+    pub const fn from_u128(value: u128) -> Self {
+        Uuid(value.to_be_bytes())
+    }
+
+    #[inline]
+    pub const fn as_u128(&self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
+    #[inline]
+    pub fn is_nil(&self) -> bool {
+        *self == Self::NIL
+    }
+}
+
+impl fmt::Display for Uuid {
+    /// The hyphenated lowercase form, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, byte) in self.0.iter().enumerate() {
+            if let 4 | 6 | 8 | 10 = idx {
+                f.write_str("-")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Uuid({})", self)
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = crate::NoserError;
+
+    /// Accepts the hyphenated form and the 32 hex digits without hyphens, in either case.
+    fn from_str(s: &str) -> crate::Result<Uuid> {
+        let hyphenated = s.len() == 36;
+        if !hyphenated && s.len() != 32 {
+            return Err(crate::NoserError::Malformed);
+        }
+
+        let mut digits = s.bytes().enumerate().filter_map(|(idx, c)| match idx {
+            8 | 13 | 18 | 23 if hyphenated => None,
+            _ => Some(c),
+        });
+
+        if hyphenated && [8, 13, 18, 23].iter().any(|&idx| s.as_bytes()[idx] != b'-') {
+            return Err(crate::NoserError::Malformed);
+        }
+
+        let mut bytes = [0; 16];
+        for byte in bytes.iter_mut() {
+            let (hi, lo) = (digits.next(), digits.next());
+            *byte = match (hi.and_then(hex), lo.and_then(hex)) {
+                (Some(hi), Some(lo)) => hi << 4 | lo,
+                _ => return Err(crate::NoserError::Malformed),
+            };
+        }
+
+        Ok(Uuid(bytes))
+    }
+}
+
+#[inline]
+fn hex(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|d| d as u8)
+}
+
+impl LiteralInnerType for Uuid {
+    const SIZE: usize = 16;
+
+    #[inline]
+    fn imprint(arena: &mut [u8]) -> crate::Result<()> {
+        if arena.len() >= Self::SIZE {
+            Ok(())
+        } else {
+            Err(crate::NoserError::undersized(Self::SIZE, arena))
+        }
+    }
+}
+
+impl Write for Uuid {
+    #[inline]
+    fn write(arena: &mut [u8], val: Uuid) {
+        arena[..Self::SIZE].copy_from_slice(&val.0)
+    }
+}
+
+impl Read for Uuid {
+    type Output = Uuid;
+
+    #[inline]
+    fn read(arena: &[u8]) -> Uuid {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&arena[..Self::SIZE]);
+        Uuid(bytes)
+    }
+}
+
+impl StructuralLiteral for Uuid {
+    #[inline]
+    fn compare(a: Uuid, b: Uuid, _: Floats) -> Option<Ordering> {
+        Some(a.cmp(&b))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl JsonLiteral for Uuid {
+    #[inline]
+    fn to_value(val: Uuid) -> Value {
+        Value::String(val.to_string())
+    }
+
+    #[inline]
+    fn from_value(value: &Value) -> crate::Result<Uuid> {
+        match value {
+            Value::String(s) => s.parse().map_err(|_| JsonError::Mismatch("uuid").into()),
+            _ => Err(JsonError::Mismatch("uuid").into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+    #[test]
+    fn rw_uuid() {
+        let arena = &mut [0; 16];
+        let id = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+
+        Uuid::write(arena, id);
+        assert_eq!(arena[..2], [0x67, 0xe5]);
+        assert_eq!(Uuid::read(arena), id);
+    }
+
+    #[test]
+    fn parse_uuid() {
+        let id: Uuid = ID.parse().unwrap();
+
+        assert_eq!(id.as_u128(), 0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+        assert_eq!(id.to_string(), ID);
+        assert_eq!(
+            "67E5504410B1426F9247BB680E5FE0C8".parse::<Uuid>().unwrap(),
+            id
+        );
+        assert!(ID.replace('-', "+").parse::<Uuid>().is_err());
+        assert!(ID[1..].parse::<Uuid>().is_err());
+        assert!("g7e5504410b1426f9247bb680e5fe0c8".parse::<Uuid>().is_err());
+    }
+
+    #[test]
+    fn json_uuid() {
+        let id: Uuid = ID.parse().unwrap();
+
+        assert_eq!(Uuid::to_value(id), Value::String(ID.into()));
+        assert_eq!(Uuid::from_value(&Uuid::to_value(id)).unwrap(), id);
+        assert!(Uuid::from_value(&Value::UInt(0)).is_err());
+    }
+}
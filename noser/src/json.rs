@@ -0,0 +1,579 @@
+use alloc::string::String;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::traits::{Json, WriteTypeInfo};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Keys are kept in insertion order so that exported json is deterministic.
+    Object(Vec<(String, Value)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonError {
+    /// The text is not valid json, the offset is the byte where parsing failed.
+    Syntax(usize),
+    /// The json value does not have the shape the noser type expects.
+    Mismatch(&'static str),
+    MissingField(&'static str),
+    UnknownVariant(String),
+    OutOfRange,
+}
+
+// A From impl would make the `map_err(Into::into)?` in derived code ambiguous.
+#[allow(clippy::from_over_into)]
+impl Into<crate::NoserError> for JsonError {
+    fn into(self) -> crate::NoserError {
+        crate::NoserError::Json(self)
+    }
+}
+
+impl Value {
+    pub fn parse(text: &str) -> Result<Value, JsonError> {
+        let mut parser = Parser {
+            bytes: text.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+
+        let value = parser.value()?;
+        parser.whitespace();
+
+        if parser.pos != parser.bytes.len() {
+            return Err(JsonError::Syntax(parser.pos));
+        }
+
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn field(&self, key: &'static str) -> crate::Result<&Value> {
+        match self {
+            Value::Object(_) => self.get(key).ok_or(JsonError::MissingField(key).into()),
+            _ => Err(JsonError::Mismatch("object").into()),
+        }
+    }
+
+    #[inline]
+    pub fn as_array(&self) -> crate::Result<&[Value]> {
+        match self {
+            Value::Array(items) => Ok(items),
+            _ => Err(JsonError::Mismatch("array").into()),
+        }
+    }
+
+    #[inline]
+    pub fn as_tuple(&self, len: usize) -> crate::Result<&[Value]> {
+        let items = self.as_array()?;
+
+        if items.len() != len {
+            return Err(JsonError::Mismatch("array of matching length").into());
+        }
+
+        Ok(items)
+    }
+
+    /// Splits an externally tagged enum value into its variant name and payload. Unit variants
+    /// are plain strings and have no payload.
+    pub fn as_variant(&self) -> crate::Result<(&str, Option<&Value>)> {
+        match self {
+            Value::String(name) => Ok((name, None)),
+            Value::Object(fields) if fields.len() == 1 => Ok((&fields[0].0, Some(&fields[0].1))),
+            _ => Err(JsonError::Mismatch("enum variant").into()),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::UInt(u) => write!(f, "{}", u),
+            // Json has no representation for NaN and the infinities.
+            Value::Float(v) if !v.is_finite() => f.write_str("null"),
+            Value::Float(v) => write!(f, "{:?}", v),
+            Value::String(s) => write_str(f, s),
+            Value::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+/// How deeply arrays and objects may nest, so hostile documents can't overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'t> {
+    bytes: &'t [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\n') | Some(b'\r') | Some(b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).cloned()
+    }
+
+    #[inline]
+    fn error<T>(&self) -> Result<T, JsonError> {
+        Err(JsonError::Syntax(self.pos))
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        self.whitespace();
+
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error()
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, JsonError> {
+        if self.bytes[self.pos..].starts_with(keyword.as_bytes()) {
+            self.pos += keyword.len();
+            Ok(value)
+        } else {
+            self.error()
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, JsonError> {
+        self.whitespace();
+
+        match self.peek() {
+            Some(b'n') => self.keyword("null", Value::Null),
+            Some(b't') => self.keyword("true", Value::Bool(true)),
+            Some(b'f') => self.keyword("false", Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') | Some(b'{') => {
+                if self.depth == MAX_DEPTH {
+                    return self.error();
+                }
+
+                self.depth += 1;
+                let value = self.nested();
+                self.depth -= 1;
+                value
+            }
+            Some(b'-') | Some(b'0'..=b'9') => self.number(),
+            _ => self.error(),
+        }
+    }
+
+    fn nested(&mut self) -> Result<Value, JsonError> {
+        match self.peek() {
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = vec![];
+
+                self.whitespace();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+
+                loop {
+                    items.push(self.value()?);
+                    self.whitespace();
+
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Value::Array(items));
+                        }
+                        _ => return self.error(),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut fields = vec![];
+
+                self.whitespace();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(fields));
+                }
+
+                loop {
+                    self.whitespace();
+                    let key = self.string()?;
+                    self.expect(b':')?;
+                    fields.push((key, self.value()?));
+                    self.whitespace();
+
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Value::Object(fields));
+                        }
+                        _ => return self.error(),
+                    }
+                }
+            }
+            _ => self.error(),
+        }
+    }
+
+    /// A number as RFC 8259 defines it, `-? (0 | [1-9][0-9]*) (.[0-9]+)? ([eE][+-]?[0-9]+)?`.
+    /// Numbers without a fraction are read as integers while they fit, `1e5` included.
+    fn number(&mut self) -> Result<Value, JsonError> {
+        let start = self.pos;
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => {
+                self.digits();
+            }
+            _ => return self.error(),
+        }
+        let integer_end = self.pos;
+
+        let fraction = self.peek() == Some(b'.');
+        if fraction {
+            self.pos += 1;
+            if self.digits() == 0 {
+                return self.error();
+            }
+        }
+
+        let mut exponent_start = None;
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            self.pos += 1;
+            exponent_start = Some(self.pos);
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                return self.error();
+            }
+        }
+
+        // The slice only contains ascii characters so this can not fail.
+        let text = |from, to| core::str::from_utf8(&self.bytes[from..to]).unwrap();
+        let integer = text(start, integer_end);
+
+        let scale = match exponent_start {
+            _ if fraction => None,
+            None => Some(1),
+            Some(at) => text(at, self.pos)
+                .parse()
+                .ok()
+                .and_then(|exponent| 10u64.checked_pow(exponent)),
+        };
+
+        let value = scale.and_then(|scale| {
+            if integer.starts_with('-') {
+                let int = integer.parse::<i64>().ok()?;
+                int.checked_mul(core::convert::TryFrom::try_from(scale).ok()?)
+                    .map(Value::Int)
+            } else {
+                integer
+                    .parse::<u64>()
+                    .ok()?
+                    .checked_mul(scale)
+                    .map(Value::UInt)
+            }
+        });
+
+        // Integers out of range fall back to floats like in other parsers.
+        match value {
+            Some(value) => Ok(value),
+            None => text(start, self.pos)
+                .parse()
+                .map(Value::Float)
+                .map_err(|_| JsonError::Syntax(start)),
+        }
+    }
+
+    /// Skips the digits at the current position, returning how many there were.
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+
+        self.pos - start
+    }
+
+    /// The four hex digits at `at`.
+    fn hex(&self, at: usize) -> Option<u32> {
+        self.bytes
+            .get(at..at + 4)
+            .and_then(|hex| core::str::from_utf8(hex).ok())
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        if self.peek() != Some(b'"') {
+            return self.error();
+        }
+        self.pos += 1;
+
+        let mut string = String::new();
+
+        loop {
+            let start = self.pos;
+            while let Some(byte) = self.peek() {
+                if byte == b'"' || byte == b'\\' {
+                    break;
+                }
+                self.pos += 1;
+            }
+
+            string.push_str(
+                core::str::from_utf8(&self.bytes[start..self.pos])
+                    .map_err(|_| JsonError::Syntax(start))?,
+            );
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(string);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let escaped = match self.hex(self.pos + 1) {
+                                // A surrogate pair, a char outside the basic plane.
+                                Some(high @ 0xd800..=0xdbff)
+                                    if self.bytes[self.pos + 5..].starts_with(b"\\u") =>
+                                {
+                                    match self.hex(self.pos + 7) {
+                                        Some(low @ 0xdc00..=0xdfff) => {
+                                            self.pos += 6;
+                                            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                                        }
+                                        _ => return self.error(),
+                                    }
+                                }
+                                Some(code) => code,
+                                None => return self.error(),
+                            };
+
+                            match core::char::from_u32(escaped) {
+                                Some(c) => {
+                                    self.pos += 4;
+                                    c
+                                }
+                                None => return self.error(),
+                            }
+                        }
+                        _ => return self.error(),
+                    };
+                    self.pos += 1;
+                    string.push(escaped);
+                }
+                _ => return self.error(),
+            }
+        }
+    }
+}
+
+/// Imprints the layout and the contents of a json value, allowing json documents to be used
+/// anywhere a [WriteTypeInfo](crate::traits::WriteTypeInfo) is expected, e.g. in `List::from`.
+pub struct JsonWriter<'v, T> {
+    value: &'v Value,
+    size: crate::Ptr,
+    phantom: PhantomData<T>,
+}
+
+impl<'v, T> JsonWriter<'v, T>
+where
+    T: Json,
+{
+    pub fn new(value: &'v Value) -> crate::Result<Self> {
+        Ok(JsonWriter {
+            value,
+            size: T::json_size(value)?,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<T> WriteTypeInfo<T> for JsonWriter<'_, T>
+where
+    T: Json,
+{
+    #[inline]
+    fn imprint(&self, arena: &mut [u8]) -> crate::Result<()> {
+        T::imprint_json(self.value, arena).map(|_| ())
+    }
+
+    #[inline]
+    fn result_size(&self) -> crate::Ptr {
+        self.size
+    }
+}
+
+/// Exports the instance of `T` at the start of the arena.
+#[inline]
+pub fn to_json<T>(arena: &[u8]) -> crate::Result<Value>
+where
+    T: Json,
+{
+    T::to_json(arena).map(|(value, _)| value)
+}
+
+/// Creates a new buffer containing `value` imprinted as an instance of `T`.
+#[inline]
+pub fn from_json<T>(value: &Value) -> crate::Result<Vec<u8>>
+where
+    T: Json,
+{
+    JsonWriter::<T>::new(value)?.create_buffer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_print() {
+        let text = r#"{"a":[1,-2,3.5,true,null],"b":"h\"i\n","c":{}}"#;
+        let value = Value::parse(text).unwrap();
+
+        assert_eq!(
+            value.get("a").unwrap().as_array().unwrap()[1],
+            Value::Int(-2)
+        );
+        assert_eq!(value.to_string(), text);
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert_eq!(Value::parse("[1,"), Err(JsonError::Syntax(3)));
+        assert_eq!(Value::parse("{} x"), Err(JsonError::Syntax(3)));
+        assert_eq!(Value::parse("\"\\x\""), Err(JsonError::Syntax(2)));
+    }
+
+    #[test]
+    fn numbers() {
+        let parse = |text| Value::parse(text).unwrap();
+
+        assert_eq!(parse("0"), Value::UInt(0));
+        assert_eq!(parse("-0"), Value::Int(0));
+        assert_eq!(parse("-12"), Value::Int(-12));
+        assert_eq!(parse("1e5"), Value::UInt(100_000));
+        assert_eq!(parse("-2E+2"), Value::Int(-200));
+        assert_eq!(parse("1.5e-1"), Value::Float(0.15));
+        assert_eq!(parse("10e-1"), Value::Float(1.0));
+
+        assert_eq!(parse("18446744073709551615"), Value::UInt(u64::MAX));
+        assert_eq!(
+            parse("18446744073709551616"),
+            Value::Float(1.8446744073709552e19)
+        );
+        assert_eq!(
+            parse("-9223372036854775809"),
+            Value::Float(-9.223372036854776e18)
+        );
+        assert_eq!(parse("1e20"), Value::Float(1e20));
+
+        assert_eq!(Value::parse("01"), Err(JsonError::Syntax(1)));
+        assert_eq!(Value::parse("[-01]"), Err(JsonError::Syntax(3)));
+        assert_eq!(Value::parse("1."), Err(JsonError::Syntax(2)));
+        assert_eq!(Value::parse("-.5"), Err(JsonError::Syntax(1)));
+        assert_eq!(Value::parse("1e"), Err(JsonError::Syntax(2)));
+        assert_eq!(Value::parse("+1"), Err(JsonError::Syntax(0)));
+        assert_eq!(Value::parse("1-2"), Err(JsonError::Syntax(1)));
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        assert!(Value::parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            Value::parse(&nested(MAX_DEPTH + 1)),
+            Err(JsonError::Syntax(MAX_DEPTH))
+        );
+        assert!(Value::parse(&"{\"a\":".repeat(100_000)).is_err());
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        let value = Value::parse(r#""\u00e9\ud83d\ude00""#).unwrap();
+        assert_eq!(value, Value::String("é😀".into()));
+
+        assert!(Value::parse(r#""\ud83d""#).is_err());
+        assert!(Value::parse(r#""\ud83d\u0041""#).is_err());
+        assert!(Value::parse(r#""\ude00""#).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! A buffer bundled with the type of its root, so a message can be stored, returned and sent
+//! without the borrow of its arena getting in the way.
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+
+use crate::diff::{apply_patch, diff, Patch};
+use crate::traits::{Build, Diff, Floats, Reborrow, Structural, WriteTypeInfo};
+
+/// Owns the buffer of a `T`, where `T` is the root type with a `'static` arena lifetime, e.g.
+/// `Message<Proto<'static>>`. Views of the root borrow the message.
+pub struct Message<T> {
+    buffer: Box<[u8]>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Message<T>
+where
+    T: for<'a> Reborrow<'a>,
+{
+    /// A new message laid out by `writer`. The buffer is validated like received bytes, a faulty
+    /// writer fails here rather than in the views of the message.
+    pub fn new(writer: &dyn WriteTypeInfo<T>) -> crate::Result<Self> {
+        Self::from_bytes(writer.create_buffer()?)
+    }
+
+    /// Takes over received bytes, which must hold exactly one valid root. Use
+    /// [Message::from_prefix] to accept bytes after the root.
+    pub fn from_bytes<B>(bytes: B) -> crate::Result<Self>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        let mut buffer = bytes.into();
+
+        let (unused, _) = <T as Reborrow>::View::build(&mut buffer)?;
+        if !unused.is_empty() {
+            return Err(crate::NoserError::Malformed);
+        }
+
+        Ok(Message {
+            buffer,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Takes over received bytes starting with a valid root and drops the bytes after it, e.g.
+    /// fields a newer version of the schema appended to the root.
+    pub fn from_prefix<B>(bytes: B) -> crate::Result<Self>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        let mut buffer = bytes.into().into_vec();
+
+        let unused = <T as Reborrow>::View::build(&mut buffer)?.0.len();
+        buffer.truncate(buffer.len() - unused);
+
+        Ok(Message {
+            buffer: buffer.into_boxed_slice(),
+            phantom: PhantomData,
+        })
+    }
+
+    /// The root, read only. Views are built from a `&mut [u8]`, so this borrows the message
+    /// mutably like [Message::get_mut], a shared borrow would alias the buffer.
+    #[inline]
+    pub fn get(&mut self) -> freyr::ReadOnly<<T as Reborrow<'_>>::View> {
+        freyr::ReadOnly::new(<T as Reborrow>::View::unchecked_create(&mut self.buffer))
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> <T as Reborrow<'_>>::View {
+        <T as Reborrow>::View::unchecked_create(&mut self.buffer)
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    #[inline]
+    pub fn into_bytes(self) -> Box<[u8]> {
+        self.buffer
+    }
+
+    /// The changes from this message to `newer`.
+    pub fn diff(&self, newer: &Self) -> crate::Result<Patch>
+    where
+        T: Diff,
+    {
+        diff::<T>(&self.buffer, &newer.buffer)
+    }
+
+    /// The message `patch` was made for, given the message it was made from.
+    pub fn patch(&self, patch: &Patch) -> crate::Result<Self> {
+        Self::from_bytes(apply_patch(&self.buffer, patch)?)
+    }
+
+    /// Runs `f` on the mutable root, marking what it writes in `dirty`.
+    #[cfg(feature = "dirty")]
+    pub fn track<F, R>(&mut self, dirty: &mut crate::dirty::Dirty, f: F) -> R
+    where
+        F: for<'v> FnOnce(<T as Reborrow<'v>>::View) -> R,
+    {
+        dirty.track(&mut self.buffer, |arena| {
+            f(<T as Reborrow>::View::unchecked_create(arena))
+        })
+    }
+
+    /// Runs `f` on the mutable root, undoing everything it wrote if it fails.
+    #[cfg(feature = "transaction")]
+    pub fn transaction<F, R>(&mut self, f: F) -> crate::Result<R>
+    where
+        F: for<'v> FnOnce(<T as Reborrow<'v>>::View) -> crate::Result<R>,
+    {
+        let mut transaction = crate::transaction::Transaction::new(&mut self.buffer);
+
+        let result =
+            transaction.apply(|arena| f(<T as Reborrow>::View::unchecked_create(arena)))?;
+        transaction.commit();
+        Ok(result)
+    }
+}
+
+impl<T> Clone for Message<T> {
+    fn clone(&self) -> Self {
+        Message {
+            buffer: self.buffer.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for Message<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Message").field(&self.buffer).finish()
+    }
+}
+
+/// Messages are equal if their roots are, compared with [Floats::Total].
+impl<T> PartialEq for Message<T>
+where
+    T: Structural,
+{
+    fn eq(&self, other: &Self) -> bool {
+        T::equal(&self.buffer, &other.buffer, Floats::Total).expect("A message holds a valid root!")
+    }
+}
+
+impl<T> Eq for Message<T> where T: Structural {}
+
+impl<T> core::hash::Hash for Message<T>
+where
+    T: Structural,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        T::hash(&self.buffer, state).expect("A message holds a valid root!");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get, List, Literal};
+
+    type Numbers = List<'static, Literal<'static, u16>>;
+
+    #[test]
+    fn get_and_mutate() {
+        let mut message = Message::<Numbers>::new(&List::with_capacity(3)).unwrap();
+
+        {
+            let numbers = message.get_mut();
+            let mut second = get!(numbers[1]);
+            second.write(40);
+        }
+
+        assert_eq!(message.get().borrow(1).read(), 40);
+        assert_eq!(message.as_bytes(), [3, 0, 0, 0, 0, 0, 40, 0, 0, 0]);
+    }
+
+    #[test]
+    fn validates_bytes() {
+        assert!(Message::<Numbers>::from_bytes(vec![1, 0, 0, 0, 5, 0]).is_ok());
+        assert!(Message::<Numbers>::from_bytes(vec![2, 0, 0, 0, 5, 0]).is_err());
+        assert!(Message::<Numbers>::from_bytes(vec![1, 0, 0, 0, 5, 0, 0]).is_err());
+    }
+
+    /// Imprints the given bytes as any type, like a faulty writer would.
+    struct Raw(&'static [u8]);
+
+    impl<T> WriteTypeInfo<T> for Raw {
+        fn imprint(&self, arena: &mut [u8]) -> crate::Result<()> {
+            arena.copy_from_slice(self.0);
+            Ok(())
+        }
+
+        fn result_size(&self) -> crate::Ptr {
+            self.0.len() as crate::Ptr
+        }
+    }
+
+    #[test]
+    fn validates_writers() {
+        assert!(Message::<Numbers>::new(&Raw(&[1, 0, 0, 0, 5, 0])).is_ok());
+        assert!(Message::<Numbers>::new(&Raw(&[2, 0, 0, 0, 5, 0])).is_err());
+        assert!(Message::<Literal<bool>>::new(&Raw(&[2])).is_err());
+    }
+
+    #[test]
+    fn ignores_appended_bytes() {
+        let message = Message::<Numbers>::from_prefix(vec![1, 0, 0, 0, 5, 0, 9, 9]).unwrap();
+        assert_eq!(message.as_bytes(), [1, 0, 0, 0, 5, 0]);
+
+        assert!(Message::<Numbers>::from_prefix(vec![2, 0, 0, 0, 5, 0]).is_err());
+    }
+
+    #[test]
+    fn patch() {
+        let old = Message::<Numbers>::from_bytes(vec![2, 0, 0, 0, 1, 0, 2, 0]).unwrap();
+        let new = Message::<Numbers>::from_bytes(vec![3, 0, 0, 0, 1, 0, 5, 0, 6, 0]).unwrap();
+
+        let patch = old.diff(&new).unwrap();
+        assert_eq!(patch.splices().len(), 3);
+        assert_eq!(old.patch(&patch).unwrap().as_bytes(), new.as_bytes());
+
+        let shrink = new.diff(&old).unwrap();
+        assert!(old.patch(&shrink).is_err());
+    }
+
+    #[test]
+    fn send_to_thread() {
+        let mut message = Message::<Literal<u64>>::new(&Literal::<u64>::with_value(9)).unwrap();
+
+        let read = std::thread::spawn(move || message.get().read())
+            .join()
+            .unwrap();
+        assert_eq!(read, 9);
+    }
+}
@@ -1,21 +1,117 @@
 #![deny(clippy::all)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
 
 pub type Ptr = u32;
 
+#[cfg(feature = "alloc")]
+pub mod builder;
+#[cfg(feature = "alloc")]
+pub mod datagram;
+#[cfg(feature = "alloc")]
+pub mod diff;
+#[cfg(feature = "dirty")]
+pub mod dirty;
+pub mod ffi;
+#[cfg(any(feature = "dirty", feature = "transaction"))]
+mod hooks;
 mod implementation;
+#[cfg(feature = "alloc")]
+pub mod json;
+#[cfg(feature = "alloc")]
+pub mod message;
+#[cfg(feature = "alloc")]
+pub mod pool;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std")]
+pub mod stream;
 pub mod traits;
+#[cfg(feature = "transaction")]
+pub mod transaction;
 pub mod writer;
 
 pub use crate::implementation::*;
 
 #[derive(Debug)]
 pub enum NoserError {
+    /// The arena is smaller than the given number of bytes. A copy of the arena is kept with the
+    /// `alloc` feature.
+    #[cfg(feature = "alloc")]
     Undersized(usize, Vec<u8>),
+    #[cfg(not(feature = "alloc"))]
+    Undersized(usize),
     IntegerOverflow,
     Malformed,
+    /// A new enum variant needs more than the given number of bytes reserved for the enum.
+    DoesNotFit(usize),
+    #[cfg(feature = "alloc")]
+    Json(json::JsonError),
+    /// An error raised by user code, e.g. a serde implementation.
+    #[cfg(feature = "alloc")]
+    Custom(String),
+    /// A stream failed, the error may be `WouldBlock` for non-blocking streams.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// A frame is longer than the given limit of its stream.
+    FrameTooLarge(usize),
+}
+
+impl NoserError {
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn undersized(size: usize, arena: &[u8]) -> Self {
+        NoserError::Undersized(size, arena.to_vec())
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[inline]
+    pub(crate) fn undersized(size: usize, _arena: &[u8]) -> Self {
+        NoserError::Undersized(size)
+    }
 }
 
-pub type Result<T> = ::std::result::Result<T, NoserError>;
+impl ::core::fmt::Display for NoserError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            #[cfg(feature = "alloc")]
+            NoserError::Undersized(size, arena) => write!(
+                f,
+                "arena of {} bytes is too small, expected at least {} bytes",
+                arena.len(),
+                size
+            ),
+            #[cfg(not(feature = "alloc"))]
+            NoserError::Undersized(size) => {
+                write!(f, "arena is too small, expected at least {} bytes", size)
+            }
+            NoserError::IntegerOverflow => f.write_str("integer overflow"),
+            NoserError::Malformed => f.write_str("malformed arena"),
+            NoserError::DoesNotFit(size) => {
+                write!(f, "variant does not fit in the {} bytes of the enum", size)
+            }
+            #[cfg(feature = "alloc")]
+            NoserError::Json(e) => write!(f, "json error: {:?}", e),
+            #[cfg(feature = "alloc")]
+            NoserError::Custom(msg) => f.write_str(msg),
+            #[cfg(feature = "std")]
+            NoserError::Io(e) => write!(f, "io error: {}", e),
+            NoserError::FrameTooLarge(limit) => {
+                write!(f, "frame is larger than the limit of {} bytes", limit)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for NoserError {}
+
+pub type Result<T> = ::core::result::Result<T, NoserError>;
 
 pub mod prelude {
     pub trait SliceExt {
@@ -29,7 +125,7 @@ pub mod prelude {
             let at = at as usize;
 
             if self.len() < at {
-                return Err(crate::NoserError::Undersized(at, self.to_vec()));
+                return Err(crate::NoserError::undersized(at, self));
             }
 
             Ok(self.split_at_mut(at))
@@ -40,7 +136,7 @@ pub mod prelude {
             let at = at as usize;
 
             if self.len() < at {
-                return Err(crate::NoserError::Undersized(at, self.to_vec()));
+                return Err(crate::NoserError::undersized(at, self));
             }
 
             Ok(self.split_at(at))
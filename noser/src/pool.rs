@@ -0,0 +1,86 @@
+//! Reusable buffers, so a loop creating a message every tick stops allocating once the pool holds
+//! enough buffers of the sizes it needs.
+use alloc::{vec, vec::Vec};
+
+/// Buffers handed back with [Pool::recycle], zeroed and ready to be imprinted again.
+#[derive(Debug, Default)]
+pub struct Pool {
+    free: Vec<Vec<u8>>,
+}
+
+impl Pool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A pool already holding `count` buffers of `size` bytes.
+    pub fn with_buffers(count: usize, size: usize) -> Self {
+        Pool {
+            free: (0..count).map(|_| vec![0; size]).collect(),
+        }
+    }
+
+    /// The number of buffers waiting to be reused.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// A zeroed buffer of `size` bytes, only allocating if no pooled buffer is large enough. The
+    /// most recently recycled buffers are preferred, their memory is more likely to be cached.
+    pub fn take(&mut self, size: usize) -> Vec<u8> {
+        let fitting = self.free.iter().rposition(|b| b.capacity() >= size);
+        let mut buffer = match fitting {
+            Some(idx) => self.free.swap_remove(idx),
+            None => self.free.pop().unwrap_or_default(),
+        };
+
+        // Recycled buffers are zeroed, so this only writes the bytes past their length.
+        buffer.resize(size, 0);
+        buffer
+    }
+
+    /// Zeroes `buffer` and keeps it for a later `take`.
+    pub fn recycle(&mut self, mut buffer: Vec<u8>) {
+        buffer.fill(0);
+        self.free.push(buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_buffers() {
+        let mut pool = Pool::with_buffers(1, 16);
+
+        let mut buffer = pool.take(8);
+        assert!(pool.is_empty());
+        buffer.copy_from_slice(&[7; 8]);
+        let at = buffer.as_ptr();
+        pool.recycle(buffer);
+
+        let buffer = pool.take(12);
+        assert_eq!(buffer.as_ptr(), at);
+        assert_eq!(buffer, [0; 12]);
+        pool.recycle(buffer);
+    }
+
+    #[test]
+    fn picks_fitting_buffers() {
+        let mut pool = Pool::new();
+        pool.recycle(Vec::with_capacity(64));
+        pool.recycle(Vec::with_capacity(4));
+
+        assert!(pool.take(32).capacity() >= 32);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.take(100).len(), 100);
+        assert_eq!(pool.take(2), [0; 2]);
+    }
+}
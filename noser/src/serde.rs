@@ -0,0 +1,757 @@
+//! A serde data format writing the same layout as the noser types.
+//!
+//! | serde                      | noser layout                                  |
+//! |----------------------------|-----------------------------------------------|
+//! | bool, integers, f32, f64   | `Literal` of the same type                    |
+//! | char                       | `Literal<char>`                               |
+//! | str, bytes                 | `&[u8]`                                       |
+//! | seq, map                   | `List` of the elements or key/value pairs     |
+//! | struct, tuple              | the fields in order, like derived structs     |
+//! | newtype struct             | the inner value                               |
+//! | unit, unit struct          | nothing                                       |
+//! | option                     | enum with the variants `None` and `Some`      |
+//! | enum                       | variant tag followed by the variant contents  |
+//!
+//! Serde does not tell the serializer how many variants an enum has, the tag is therefore always
+//! `VARIANT_BYTES` wide. This matches derived enums with 2 to 256 variants.
+//!
+//! The format is not self describing, `deserialize_any` and friends are not supported.
+
+use ::serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use ::serde::ser::{self, Serialize};
+
+use crate::prelude::SliceExt;
+use crate::traits::{LiteralInnerType, Read, Write};
+use crate::{read_var_len_int, write_var_len_int, ListLen, NoserError};
+
+pub const VARIANT_BYTES: usize = 1;
+
+impl ser::Error for NoserError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        NoserError::Custom(msg.to_string())
+    }
+}
+
+impl de::Error for NoserError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        NoserError::Custom(msg.to_string())
+    }
+}
+
+pub fn to_bytes<T>(value: &T) -> crate::Result<Vec<u8>>
+where
+    T: Serialize + ?Sized,
+{
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner())
+}
+
+/// Deserializes a `T` from the start of `input`, any trailing bytes are ignored.
+pub fn from_bytes<'de, T>(input: &'de [u8]) -> crate::Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    T::deserialize(&mut Deserializer::new(input))
+}
+
+pub struct Serializer {
+    output: Vec<u8>,
+}
+
+impl Serializer {
+    pub fn new() -> Self {
+        Serializer { output: vec![] }
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.output
+    }
+
+    #[inline]
+    fn literal<T>(&mut self, val: T)
+    where
+        T: LiteralInnerType + Write,
+    {
+        let start = self.output.len();
+        self.output.resize(start + T::SIZE, 0);
+        T::write(&mut self.output[start..], val);
+    }
+
+    #[inline]
+    fn tag(&mut self, variant_index: u32) -> crate::Result<()> {
+        if u64::from(variant_index) >> (VARIANT_BYTES * 8) != 0 {
+            return Err(NoserError::IntegerOverflow);
+        }
+
+        let start = self.output.len();
+        self.output.resize(start + VARIANT_BYTES, 0);
+        write_var_len_int(
+            &mut self.output[start..],
+            VARIANT_BYTES,
+            u64::from(variant_index),
+        );
+        Ok(())
+    }
+}
+
+impl Default for Serializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = NoserError;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> crate::Result<()> {
+        self.literal(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> crate::Result<()> {
+        self.literal(v);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> crate::Result<()> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> crate::Result<()> {
+        if v.len() > crate::Ptr::MAX as usize {
+            return Err(NoserError::IntegerOverflow);
+        }
+
+        self.literal(v.len() as crate::Ptr);
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> crate::Result<()> {
+        self.tag(0)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> crate::Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.tag(1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+    ) -> crate::Result<()> {
+        self.tag(variant_index)
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> crate::Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+        value: &T,
+    ) -> crate::Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.tag(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> crate::Result<Compound<'a>> {
+        Ok(Compound::list(self))
+    }
+
+    fn serialize_tuple(self, _: usize) -> crate::Result<Compound<'a>> {
+        Ok(Compound::fields(self))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> crate::Result<Compound<'a>> {
+        Ok(Compound::fields(self))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> crate::Result<Compound<'a>> {
+        self.tag(variant_index)?;
+        Ok(Compound::fields(self))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> crate::Result<Compound<'a>> {
+        Ok(Compound::list(self))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> crate::Result<Compound<'a>> {
+        Ok(Compound::fields(self))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> crate::Result<Compound<'a>> {
+        self.tag(variant_index)?;
+        Ok(Compound::fields(self))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Serializes the elements of compound values. Sequences and maps are written as lists, since
+/// serde does not always know their length up front the length is patched in at the end.
+pub struct Compound<'a> {
+    ser: &'a mut Serializer,
+    len_at: Option<usize>,
+    len: usize,
+}
+
+impl<'a> Compound<'a> {
+    fn list(ser: &'a mut Serializer) -> Self {
+        let len_at = ser.output.len();
+        ser.output.resize(len_at + ListLen::SIZE, 0);
+
+        Compound {
+            ser,
+            len_at: Some(len_at),
+            len: 0,
+        }
+    }
+
+    fn fields(ser: &'a mut Serializer) -> Self {
+        Compound {
+            ser,
+            len_at: None,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn element<T>(&mut self, value: &T) -> crate::Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.len += 1;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn finish(self) -> crate::Result<()> {
+        if let Some(len_at) = self.len_at {
+            if self.len > ListLen::MAX as usize {
+                return Err(NoserError::IntegerOverflow);
+            }
+
+            ListLen::write(&mut self.ser.output[len_at..], self.len as ListLen);
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! compound {
+    ($trait:ident, $method:ident) => {
+        impl ser::$trait for Compound<'_> {
+            type Ok = ();
+            type Error = NoserError;
+
+            #[inline]
+            fn $method<T>(&mut self, value: &T) -> crate::Result<()>
+            where
+                T: Serialize + ?Sized,
+            {
+                self.element(value)
+            }
+
+            #[inline]
+            fn end(self) -> crate::Result<()> {
+                self.finish()
+            }
+        }
+    };
+    ($trait:ident) => {
+        impl ser::$trait for Compound<'_> {
+            type Ok = ();
+            type Error = NoserError;
+
+            #[inline]
+            fn serialize_field<T>(&mut self, _: &'static str, value: &T) -> crate::Result<()>
+            where
+                T: Serialize + ?Sized,
+            {
+                self.element(value)
+            }
+
+            #[inline]
+            fn end(self) -> crate::Result<()> {
+                self.finish()
+            }
+        }
+    };
+}
+
+compound!(SerializeSeq, serialize_element);
+compound!(SerializeTuple, serialize_element);
+compound!(SerializeTupleStruct, serialize_field);
+compound!(SerializeTupleVariant, serialize_field);
+compound!(SerializeStruct);
+compound!(SerializeStructVariant);
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = NoserError;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> crate::Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        // A map is a list of key value pairs, only count the pair once.
+        self.element(key)
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> crate::Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut *self.ser)
+    }
+
+    #[inline]
+    fn end(self) -> crate::Result<()> {
+        self.finish()
+    }
+}
+
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn new(input: &'de [u8]) -> Self {
+        Deserializer { input }
+    }
+
+    /// The bytes following everything deserialized so far.
+    pub fn remaining(&self) -> &'de [u8] {
+        self.input
+    }
+
+    #[inline]
+    fn take(&mut self, len: crate::Ptr) -> crate::Result<&'de [u8]> {
+        let (left, right) = self.input.noser_split_imut(len)?;
+        self.input = right;
+        Ok(left)
+    }
+
+    #[inline]
+    fn literal<T>(&mut self) -> crate::Result<T::Output>
+    where
+        T: Read,
+    {
+        let val = T::read_safe(self.input)?;
+        self.input = &self.input[T::SIZE..];
+        Ok(val)
+    }
+
+    #[inline]
+    fn tag(&mut self) -> crate::Result<u64> {
+        let left = self.take(VARIANT_BYTES as crate::Ptr)?;
+        Ok(read_var_len_int(left, VARIANT_BYTES))
+    }
+
+    #[inline]
+    fn bytes(&mut self) -> crate::Result<&'de [u8]> {
+        let len = self.literal::<crate::Ptr>()?;
+        self.take(len)
+    }
+
+    #[inline]
+    fn str(&mut self) -> crate::Result<&'de str> {
+        std::str::from_utf8(self.bytes()?).map_err(|_| NoserError::Malformed)
+    }
+}
+
+macro_rules! deserialize_literal {
+    ($($method:ident => $visit:ident($ty:ident),)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> crate::Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.literal::<$ty>()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = NoserError;
+
+    fn deserialize_any<V>(self, _: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom("the noser format is not self describing"))
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.literal::<u8>()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            _ => Err(NoserError::Malformed),
+        }
+    }
+
+    deserialize_literal! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_char(self.literal::<char>()?.ok_or(NoserError::Malformed)?)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.str()?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(self.bytes()?)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.tag()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            _ => Err(NoserError::Malformed),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _: &'static str, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.literal::<ListLen>()?;
+        visitor.visit_seq(Elements {
+            de: self,
+            remaining: len as usize,
+        })
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Elements {
+            de: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.literal::<ListLen>()?;
+        visitor.visit_map(Elements {
+            de: self,
+            remaining: len as usize,
+        })
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V>(self, _: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom(
+            "the noser format does not store identifiers",
+        ))
+    }
+
+    fn deserialize_ignored_any<V>(self, _: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom("the noser format is not self describing"))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct Elements<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'_, 'de> {
+    type Error = NoserError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> crate::Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        // Never trust a length read from the buffer for preallocation.
+        Some(self.remaining.min(self.de.input.len()))
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Elements<'_, 'de> {
+    type Error = NoserError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> crate::Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        de::SeqAccess::next_element_seed(self, seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> crate::Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        de::SeqAccess::size_hint(self)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = NoserError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> crate::Result<(V::Value, Self)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let tag = self.tag()?;
+        let variant = seed.deserialize(IntoDeserializer::<NoserError>::into_deserializer(tag))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = NoserError;
+
+    fn unit_variant(self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> crate::Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> crate::Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
@@ -0,0 +1,460 @@
+//! Messages sent over a byte stream, e.g. a TCP connection or a replay file. Every frame is the
+//! `Ptr` length of a message, a `u64` fingerprint of its bytes and the message itself. The
+//! fingerprint rejects corrupted frames, e.g. the last frame of a replay file cut short while
+//! writing it.
+use std::hash::Hasher;
+use std::io::{self, ErrorKind};
+use std::marker::PhantomData;
+
+use crate::message::Message;
+use crate::traits::{Build, LiteralInnerType, Read, Reborrow, StableHasher, Write, WriteTypeInfo};
+
+/// The length and fingerprint in front of every message.
+const HEADER_SIZE: usize = crate::Ptr::SIZE + u64::SIZE;
+
+/// The longest frame a [FrameReader] accepts unless told otherwise, 16 MiB.
+pub const DEFAULT_MAX_FRAME: crate::Ptr = 16 << 20;
+
+/// The buffer grows by at most this many bytes ahead of the bytes that arrived, so a length alone
+/// can't make the reader allocate the whole frame.
+const READ_CHUNK: usize = 64 << 10;
+
+#[inline]
+fn fingerprint(message: &[u8]) -> u64 {
+    let mut state = StableHasher::default();
+    state.write(message);
+    state.finish()
+}
+
+/// Reads the frames of a stream into a buffer that is reused for every frame, yielding a
+/// validated view of each message. `T` is the root type with a `'static` arena lifetime, like
+/// for [Message](crate::message::Message).
+pub struct FrameReader<R, T> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// The bytes of the current frame read so far, length included.
+    filled: usize,
+    max_frame: usize,
+    /// Set once a frame was too large, its payload was never read so the frames after it can't
+    /// be found.
+    poisoned: bool,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<R, T> FrameReader<R, T>
+where
+    R: io::Read,
+    T: for<'a> Reborrow<'a>,
+{
+    pub fn new(reader: R) -> Self {
+        FrameReader {
+            reader,
+            buffer: vec![],
+            filled: 0,
+            max_frame: DEFAULT_MAX_FRAME as usize,
+            poisoned: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Rejects frames longer than `max_frame` bytes before reading them, so a peer can't make
+    /// the buffer grow without bounds. Defaults to [DEFAULT_MAX_FRAME]. The stream can't be read
+    /// past a rejected frame, every later call fails with the same error.
+    pub fn with_max_frame(mut self, max_frame: crate::Ptr) -> Self {
+        self.max_frame = max_frame as usize;
+        self
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// The next message, or `None` if the stream ended between two frames. A frame cut short by
+    /// the end of the stream is [Undersized](crate::NoserError::Undersized), one whose
+    /// fingerprint doesn't match is [Malformed](crate::NoserError::Malformed). If the stream
+    /// fails, e.g. with `WouldBlock`, the bytes read so far are kept and the next call resumes
+    /// the frame.
+    pub fn next_message(&mut self) -> crate::Result<Option<<T as Reborrow<'_>>::View>> {
+        if self.poisoned {
+            return Err(crate::NoserError::FrameTooLarge(self.max_frame));
+        }
+
+        if !self.fill(HEADER_SIZE)? {
+            return Ok(None);
+        }
+
+        let len = crate::Ptr::read(&self.buffer) as usize;
+        if len > self.max_frame {
+            self.poisoned = true;
+            return Err(crate::NoserError::FrameTooLarge(self.max_frame));
+        }
+
+        let size = HEADER_SIZE + len;
+        self.fill(size)?;
+        self.filled = 0;
+
+        if u64::read(&self.buffer[crate::Ptr::SIZE..])
+            != fingerprint(&self.buffer[HEADER_SIZE..size])
+        {
+            return Err(crate::NoserError::Malformed);
+        }
+
+        let frame = &mut self.buffer[HEADER_SIZE..size];
+        let (unused, view) = <T as Reborrow>::View::build(frame)?;
+        if !unused.is_empty() {
+            return Err(crate::NoserError::Malformed);
+        }
+
+        Ok(Some(view))
+    }
+
+    /// Reads until the first `size` bytes of the frame are in the buffer. Returns false if the
+    /// stream ended before the frame started.
+    fn fill(&mut self, size: usize) -> crate::Result<bool> {
+        while self.filled < size {
+            let end = size.min(self.filled + READ_CHUNK);
+            if self.buffer.len() < end {
+                self.buffer.resize(end, 0);
+            }
+
+            match self.reader.read(&mut self.buffer[self.filled..end]) {
+                Ok(0) if self.filled == 0 => return Ok(false),
+                Ok(0) => {
+                    let partial = self.buffer[..self.filled].to_vec();
+                    self.filled = 0;
+                    return Err(crate::NoserError::Undersized(size, partial));
+                }
+                Ok(read) => self.filled += read,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(crate::NoserError::Io(e)),
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Writes messages as frames, optionally coalescing small frames into one write call. Pending
+/// frames are written once the batch is full, by `flush` and when the writer is dropped.
+pub struct FrameWriter<W>
+where
+    W: io::Write,
+{
+    writer: W,
+    batch: Vec<u8>,
+    batch_size: usize,
+}
+
+impl<W> FrameWriter<W>
+where
+    W: io::Write,
+{
+    /// A writer writing every frame with a call of its own.
+    pub fn new(writer: W) -> Self {
+        FrameWriter {
+            writer,
+            batch: vec![],
+            batch_size: 0,
+        }
+    }
+
+    /// Holds frames back until at least `batch_size` bytes are pending.
+    pub fn with_batching(mut self, batch_size: usize) -> Self {
+        self.batch = Vec::with_capacity(batch_size);
+        self.batch_size = batch_size;
+        self
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// The bytes of the frames not written yet.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.batch.len()
+    }
+
+    /// Writes the bytes of one message.
+    pub fn write_bytes(&mut self, message: &[u8]) -> crate::Result<()> {
+        let start = self.append_header(message.len())?;
+        self.batch.extend_from_slice(message);
+
+        self.finish_frame(start)
+    }
+
+    #[inline]
+    pub fn write_message<T>(&mut self, message: &Message<T>) -> crate::Result<()>
+    where
+        T: for<'a> Reborrow<'a>,
+    {
+        self.write_bytes(message.as_bytes())
+    }
+
+    /// Writes a message laid out by `writer`, straight into the batch.
+    pub fn imprint<T>(&mut self, writer: &dyn WriteTypeInfo<T>) -> crate::Result<()> {
+        let size = writer.result_size() as usize;
+        let start = self.append_header(size)?;
+
+        self.batch.resize(start + HEADER_SIZE + size, 0);
+        if let Err(e) = writer.imprint(&mut self.batch[start + HEADER_SIZE..]) {
+            self.batch.truncate(start);
+            return Err(e);
+        }
+
+        self.finish_frame(start)
+    }
+
+    /// Writes the pending frames and flushes the stream.
+    pub fn flush(&mut self) -> crate::Result<()> {
+        self.write_batch()?;
+        self.writer.flush().map_err(crate::NoserError::Io)
+    }
+
+    /// Appends the header of a frame, returning where it starts.
+    #[inline]
+    fn append_header(&mut self, len: usize) -> crate::Result<usize> {
+        if len > crate::Ptr::MAX as usize {
+            return Err(crate::NoserError::IntegerOverflow);
+        }
+
+        let start = self.batch.len();
+        self.batch.resize(start + HEADER_SIZE, 0);
+        crate::Ptr::write(&mut self.batch[start..], len as crate::Ptr);
+
+        Ok(start)
+    }
+
+    /// Fingerprints the frame starting at `start`, writing the batch if it is full.
+    #[inline]
+    fn finish_frame(&mut self, start: usize) -> crate::Result<()> {
+        let (header, message) = self.batch[start..].split_at_mut(HEADER_SIZE);
+        u64::write(&mut header[crate::Ptr::SIZE..], fingerprint(message));
+
+        if self.batch.len() >= self.batch_size {
+            self.write_batch()?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn write_batch(&mut self) -> crate::Result<()> {
+        if !self.batch.is_empty() {
+            self.writer
+                .write_all(&self.batch)
+                .map_err(crate::NoserError::Io)?;
+            self.batch.clear();
+        }
+
+        Ok(())
+    }
+}
+
+impl<W> Drop for FrameWriter<W>
+where
+    W: io::Write,
+{
+    /// Errors are ignored, call `flush` to handle them.
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{List, Literal};
+
+    type Numbers = List<'static, Literal<'static, u16>>;
+
+    /// Hands out at most `chunk` bytes per read, failing with `WouldBlock` every other read.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        chunk: usize,
+        block: bool,
+    }
+
+    impl io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.block = !self.block;
+            if self.block {
+                return Err(ErrorKind::WouldBlock.into());
+            }
+
+            let len = self.chunk.min(buf.len()).min(self.bytes.len());
+            buf[..len].copy_from_slice(&self.bytes[..len]);
+            self.bytes = &self.bytes[len..];
+            Ok(len)
+        }
+    }
+
+    /// Counts the write calls it gets.
+    #[derive(Default)]
+    struct Calls {
+        bytes: Vec<u8>,
+        calls: usize,
+    }
+
+    impl io::Write for Calls {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.calls += 1;
+            self.bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn frames() -> Vec<u8> {
+        let mut bytes = vec![];
+        {
+            let mut writer = FrameWriter::new(&mut bytes);
+            writer.write_bytes(&[3, 0, 0, 0, 1, 0, 2, 0, 3, 0]).unwrap();
+            writer
+                .imprint(&List::<Literal<u16>>::with_capacity(0))
+                .unwrap();
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn reads_frames() {
+        let bytes = frames();
+        let mut reader = FrameReader::<_, Numbers>::new(&bytes[..]);
+
+        let numbers = reader.next_message().unwrap().unwrap();
+        assert_eq!(numbers.capacity(), 3);
+        assert_eq!(numbers.borrow(2).read(), 3);
+
+        assert_eq!(reader.next_message().unwrap().unwrap().capacity(), 0);
+        assert!(reader.next_message().unwrap().is_none());
+    }
+
+    #[test]
+    fn resumes_partial_reads() {
+        let bytes = frames();
+        let mut reader = FrameReader::<_, Numbers>::new(Trickle {
+            bytes: &bytes,
+            chunk: 3,
+            block: false,
+        });
+
+        let mut capacities = vec![];
+        loop {
+            match reader.next_message() {
+                Ok(Some(numbers)) => capacities.push(numbers.capacity()),
+                Ok(None) => break,
+                Err(crate::NoserError::Io(ref e)) if e.kind() == ErrorKind::WouldBlock => (),
+                Err(e) => panic!("{}", e),
+            }
+        }
+
+        assert_eq!(capacities, [3, 0]);
+    }
+
+    #[test]
+    fn rejects_bad_frames() {
+        let bytes = frames();
+        let rejects = |bytes: &[u8]| {
+            let mut reader = FrameReader::<_, Numbers>::new(bytes);
+            reader.next_message().map(|numbers| numbers.is_some())
+        };
+
+        assert!(freyr::matches!(
+            rejects(&bytes[..20]),
+            Err(crate::NoserError::Undersized(22, _))
+        ));
+
+        // The frame after the rejected one is never parsed from its payload.
+        let mut limited = FrameReader::<_, Numbers>::new(&bytes[..]).with_max_frame(8);
+        for _ in 0..2 {
+            assert!(freyr::matches!(
+                limited.next_message(),
+                Err(crate::NoserError::FrameTooLarge(8))
+            ));
+        }
+
+        let mut corrupted = bytes.clone();
+        corrupted[14] = 9;
+        assert!(freyr::matches!(
+            rejects(&corrupted),
+            Err(crate::NoserError::Malformed)
+        ));
+
+        let mut trailing = vec![];
+        FrameWriter::new(&mut trailing)
+            .write_bytes(&[1, 0, 0, 0, 5, 0, 0])
+            .unwrap();
+        assert!(freyr::matches!(
+            rejects(&trailing),
+            Err(crate::NoserError::Malformed)
+        ));
+
+        let mut short = vec![];
+        FrameWriter::new(&mut short)
+            .write_bytes(&[2, 0, 0, 0, 5, 0])
+            .unwrap();
+        assert!(rejects(&short).is_err());
+    }
+
+    #[test]
+    fn grows_as_bytes_arrive() {
+        let mut header = vec![0; HEADER_SIZE];
+        crate::Ptr::write(&mut header, DEFAULT_MAX_FRAME);
+        header.extend_from_slice(&[0; 100]);
+
+        let mut reader = FrameReader::<_, Numbers>::new(&header[..]);
+        assert!(freyr::matches!(
+            reader.next_message(),
+            Err(crate::NoserError::Undersized(_, _))
+        ));
+        assert!(reader.buffer.capacity() < 4 * READ_CHUNK);
+
+        crate::Ptr::write(&mut header, DEFAULT_MAX_FRAME + 1);
+        let mut reader = FrameReader::<_, Numbers>::new(&header[..]);
+        assert!(freyr::matches!(
+            reader.next_message(),
+            Err(crate::NoserError::FrameTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn batches_writes() {
+        let mut calls = Calls::default();
+        {
+            let mut writer = FrameWriter::new(&mut calls).with_batching(30);
+            for _ in 0..3 {
+                writer.write_bytes(&[0, 0, 0, 0]).unwrap();
+            }
+            assert_eq!(writer.pending(), 16);
+        }
+        assert_eq!(calls.calls, 2);
+
+        let mut reader = FrameReader::<_, Numbers>::new(&calls.bytes[..]);
+        for _ in 0..3 {
+            assert_eq!(reader.next_message().unwrap().unwrap().capacity(), 0);
+        }
+        assert!(reader.next_message().unwrap().is_none());
+    }
+}
@@ -50,3 +50,10 @@ pub unsafe trait Build<'a>: Sized {
         Ok(freyr::ReadOnly::new(mut_this))
     }
 }
+
+/// Names a type built from an arena with the arena lifetime swapped for `'a`, so containers such
+/// as [Message](crate::message::Message) can refer to `Proto<'static>` and hand out a
+/// `Proto<'a>` borrowing their buffer. `Build` derives this for the `'static` version of a type.
+pub trait Reborrow<'a> {
+    type View: Build<'a>;
+}
@@ -0,0 +1,52 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::prelude::SliceExt;
+use crate::traits::size::Sizeable;
+
+/// Copies an instance from one arena into another, e.g. to forward part of a message. Like
+/// [Json](crate::traits::Json) this works directly on the arenas. The copy is compacted, a
+/// [DynamicEnumWrapper](crate::DynamicEnumWrapper) only keeps room for its current variant.
+pub trait CopyInto {
+    /// The amount of bytes `copy_into` writes for the instance at the start of `from`, and the
+    /// unused rest of `from`.
+    fn copy_size(from: &[u8]) -> crate::Result<(crate::Ptr, &[u8])>;
+
+    /// Copies the instance at the start of `from` to the start of `to`, returning the unused
+    /// rests of both arenas.
+    fn copy_into<'f, 't>(
+        from: &'f [u8],
+        to: &'t mut [u8],
+    ) -> crate::Result<(&'f [u8], &'t mut [u8])>;
+
+    /// Copies the instance at the start of `from` into a buffer of its own.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn copy_to_vec(from: &[u8]) -> crate::Result<Vec<u8>> {
+        let (size, _) = Self::copy_size(from)?;
+        let mut buffer = alloc::vec![0; size as usize];
+
+        Self::copy_into(from, &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// `CopyInto::copy_size` for types that are copied byte for byte.
+#[inline]
+pub(crate) fn raw_copy_size<T: Sizeable>(from: &[u8]) -> crate::Result<(crate::Ptr, &[u8])> {
+    let size = T::in_bounds(from)?;
+    Ok((size, &from[size as usize..]))
+}
+
+/// `CopyInto::copy_into` for types that are copied byte for byte.
+#[inline]
+pub(crate) fn raw_copy_into<'f, 't, T: Sizeable>(
+    from: &'f [u8],
+    to: &'t mut [u8],
+) -> crate::Result<(&'f [u8], &'t mut [u8])> {
+    let (size, rest) = raw_copy_size::<T>(from)?;
+    let (target, unused) = to.noser_split(size)?;
+
+    target.copy_from_slice(&from[..size as usize]);
+    Ok((rest, unused))
+}
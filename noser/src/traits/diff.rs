@@ -0,0 +1,27 @@
+use crate::diff::Patch;
+use crate::traits::size::Sizeable;
+
+/// Records how one instance changes into another, to send only what changed since an earlier
+/// state. Like [Json](crate::traits::Json) this works directly on the arenas. Unchanged parts are
+/// skipped, lists are compared item by item and only grow or shrink at their end.
+pub trait Diff: Sizeable {
+    /// Records the changes from the instance at the start of `old` to the one at the start of
+    /// `new` in `patch`, returning the unused rests of both arenas. `old` is always the rest of
+    /// the buffer the patch was started for.
+    fn diff<'o, 'n>(
+        old: &'o [u8],
+        new: &'n [u8],
+        patch: &mut Patch,
+    ) -> crate::Result<(&'o [u8], &'n [u8])>;
+}
+
+/// `Diff::diff` for types that are compared byte for byte.
+#[inline]
+pub(crate) fn raw_diff<'o, 'n, T: Sizeable>(
+    old: &'o [u8],
+    new: &'n [u8],
+    patch: &mut Patch,
+) -> crate::Result<(&'o [u8], &'n [u8])> {
+    let (old_size, new_size) = (T::in_bounds(old)?, T::in_bounds(new)?);
+    patch.record(old, old_size, new, new_size)
+}
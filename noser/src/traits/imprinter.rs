@@ -1,15 +1,33 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 pub trait WriteTypeInfo<T> {
     fn imprint(&self, arena: &mut [u8]) -> crate::Result<()>;
 
     fn result_size(&self) -> crate::Ptr;
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn create_buffer(&self) -> crate::Result<Vec<u8>> {
-        let mut buffer = vec![0; self.result_size() as usize];
+        let mut buffer = alloc::vec![0; self.result_size() as usize];
 
         self.imprint(&mut buffer)?;
         Ok(buffer)
     }
+
+    /// Like `create_buffer`, with a buffer taken from `pool`. Hand it back with
+    /// [Pool::recycle](crate::pool::Pool::recycle) once the message is done.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn create_buffer_in(&self, pool: &mut crate::pool::Pool) -> crate::Result<Vec<u8>> {
+        let mut buffer = pool.take(self.result_size() as usize);
+
+        if let Err(e) = self.imprint(&mut buffer) {
+            pool.recycle(buffer);
+            return Err(e);
+        }
+        Ok(buffer)
+    }
 }
 
 pub trait DefaultWriter: Sized {
@@ -29,6 +47,7 @@ pub trait DefaultWriter: Sized {
         Self::writer().imprint(arena)
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn buffer() -> crate::Result<Vec<u8>>
     where
@@ -36,4 +55,13 @@ pub trait DefaultWriter: Sized {
     {
         Self::writer().create_buffer()
     }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn buffer_in(pool: &mut crate::pool::Pool) -> crate::Result<Vec<u8>>
+    where
+        Self: 'static,
+    {
+        Self::writer().create_buffer_in(pool)
+    }
 }
@@ -0,0 +1,21 @@
+use crate::json::Value;
+use crate::traits::{Read, Write};
+
+/// Conversion between a noser type and json. Like [Sizeable](crate::traits::size::Sizeable)
+/// this works directly on the arena, exporting never needs a view of the type.
+pub trait Json {
+    /// Exports the instance at the start of the arena, returning the unused rest of the arena.
+    fn to_json(_: &[u8]) -> crate::Result<(Value, &[u8])>;
+
+    /// The amount of bytes `imprint_json` needs to imprint the value.
+    fn json_size(_: &Value) -> crate::Result<crate::Ptr>;
+
+    /// Imprints both the layout and the contents of the value, returning the unused rest of the
+    /// arena.
+    fn imprint_json<'n>(_: &Value, _: &'n mut [u8]) -> crate::Result<&'n mut [u8]>;
+}
+
+pub trait JsonLiteral: Read + Write + Sized {
+    fn to_value(_: Self::Output) -> Value;
+    fn from_value(_: &Value) -> crate::Result<Self>;
+}
@@ -2,4 +2,12 @@ pub trait LiteralInnerType {
     const SIZE: usize;
 
     fn imprint(_: &mut [u8]) -> crate::Result<()>;
+
+    /// Checks the `SIZE` bytes at the start of the arena are a canonical encoding of a value,
+    /// e.g. a bool has to be 0 or 1. [Literal](crate::Literal) checks this when it is built, so
+    /// reads of built literals never see other encodings. Accepts anything by default.
+    #[inline]
+    fn validate(_: &[u8]) -> crate::Result<()> {
+        Ok(())
+    }
 }
@@ -1,14 +1,26 @@
 mod build;
+mod copy;
+#[cfg(feature = "alloc")]
+mod diff;
 mod imprinter;
+#[cfg(feature = "alloc")]
+mod json;
 mod literal;
 mod read;
 pub mod size;
 mod static_enum;
+mod structural;
 mod write;
 
 pub use self::build::*;
+pub use self::copy::*;
+#[cfg(feature = "alloc")]
+pub use self::diff::*;
 pub use self::imprinter::*;
+#[cfg(feature = "alloc")]
+pub use self::json::*;
 pub use self::literal::*;
 pub use self::read::*;
 pub use self::static_enum::*;
+pub use self::structural::*;
 pub use self::write::*;
@@ -5,10 +5,12 @@ pub trait Read: LiteralInnerType {
 
     fn read(_: &[u8]) -> Self::Output;
 
+    /// Like `read`, but fails if `arena` is too small or doesn't hold a valid value.
     fn read_safe(arena: &[u8]) -> crate::Result<Self::Output> {
         if arena.len() < Self::SIZE {
-            Err(crate::NoserError::Undersized(Self::SIZE, arena.to_vec()))
+            Err(crate::NoserError::undersized(Self::SIZE, arena))
         } else {
+            Self::validate(arena)?;
             Ok(Self::read(arena))
         }
     }
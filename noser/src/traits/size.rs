@@ -1,5 +1,5 @@
 pub trait SizeStrategy {
-    type ErrorType: Into<crate::NoserError> + ::std::fmt::Debug;
+    type ErrorType: Into<crate::NoserError> + ::core::fmt::Debug;
 
     fn dynamic() -> bool;
 }
@@ -45,10 +45,7 @@ pub trait Sizeable {
         let size = Self::read_size(arena).map_err(Into::into)?;
 
         if arena.len() < size as usize {
-            return Err(crate::NoserError::Undersized(
-                size as usize,
-                arena.to_owned(),
-            ));
+            return Err(crate::NoserError::undersized(size as usize, arena));
         }
 
         Ok(size)
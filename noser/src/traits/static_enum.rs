@@ -23,3 +23,11 @@ pub trait StaticEnum<'a>: Sized {
     fn construct_variant(_: &Self::VariantEnum, _: &'a mut [u8]) -> crate::Result<Self>;
     fn unchecked_construct_variant(_: &Self::VariantEnum, _: &'a mut [u8]) -> Self;
 }
+
+/// A dynamically sized enum whose variant can be switched in place inside a
+/// [DynamicEnumWrapper](crate::DynamicEnumWrapper).
+pub trait DynamicEnum<'a>: crate::traits::Build<'a> + crate::traits::size::DynamicSizeable {
+    type VariantEnum: Tagged;
+
+    const VARIANT_BYTES: usize;
+}
@@ -0,0 +1,76 @@
+use core::cmp::Ordering;
+use core::hash::Hasher;
+
+use crate::traits::Read;
+
+/// How floats are compared by [Structural::compare].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Floats {
+    /// IEEE 754 comparison, NaN is unordered with every float, itself included, and
+    /// `-0.0 == 0.0`.
+    Ieee,
+    /// A total order, NaN equals a NaN with the same bits and `-0.0 < 0.0`. This is the order
+    /// [Structural::hash] agrees with.
+    Total,
+}
+
+/// Compares and hashes the contents of two instances, which may live in different arenas. Like
+/// [Json](crate::traits::Json) this works directly on the arenas.
+pub trait Structural {
+    /// Compares the instances at the start of both arenas in field order, lists compare like
+    /// slices. Returns `None` if the instances are unordered, and the unused rests of both
+    /// arenas. The rests are only complete if the instances are equal.
+    #[allow(clippy::type_complexity)]
+    fn compare<'a, 'b>(
+        a: &'a [u8],
+        b: &'b [u8],
+        floats: Floats,
+    ) -> crate::Result<(Option<Ordering>, &'a [u8], &'b [u8])>;
+
+    /// Feeds the contents of the instance at the start of the arena to `state`, returning the
+    /// unused rest of the arena. Padding is never hashed.
+    fn hash<'a, H: Hasher>(arena: &'a [u8], state: &mut H) -> crate::Result<&'a [u8]>;
+
+    #[inline]
+    fn equal(a: &[u8], b: &[u8], floats: Floats) -> crate::Result<bool> {
+        Ok(Self::compare(a, b, floats)?.0 == Some(Ordering::Equal))
+    }
+
+    /// A hash of the contents that is the same across runs, platforms and versions of noser.
+    #[inline]
+    fn content_hash(arena: &[u8]) -> crate::Result<u64> {
+        let mut state = StableHasher::default();
+
+        Self::hash(arena, &mut state)?;
+        Ok(state.finish())
+    }
+}
+
+pub trait StructuralLiteral: Read {
+    fn compare(a: Self::Output, b: Self::Output, floats: Floats) -> Option<Ordering>;
+}
+
+/// 64 bit FNV-1a, unlike the hashers of std its output never changes.
+#[derive(Debug, Clone, Copy)]
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    #[inline]
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! All or nothing updates of a buffer. While [Transaction::apply] runs, the bytes its views are
+//! about to overwrite are saved, so a failed batch of updates can be rolled back. Writes through a
+//! plain `&mut [u8]` aren't seen, call [journal] on the bytes before overwriting them.
+
+/// The overwritten bytes, in the order they were overwritten.
+#[derive(Debug, Default)]
+struct Journal {
+    entries: Vec<(crate::Ptr, crate::Ptr)>,
+    saved: Vec<u8>,
+}
+
+/// Updates of an arena that are rolled back unless committed, dropping the transaction rolls
+/// back as well.
+#[derive(Debug)]
+pub struct Transaction<'a> {
+    arena: &'a mut [u8],
+    journal: Journal,
+}
+
+impl<'a> Transaction<'a> {
+    pub fn new(arena: &'a mut [u8]) -> Self {
+        Transaction {
+            arena,
+            journal: Journal::default(),
+        }
+    }
+
+    /// Runs `f` on the arena, journaling the bytes written through views of it on this thread.
+    pub fn apply<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut [u8]) -> R,
+    {
+        crate::hooks::watch(&mut self.journal, self.arena, f)
+    }
+
+    /// The number of bytes journaled so far, counting bytes written twice twice.
+    #[inline]
+    pub fn journaled(&self) -> usize {
+        self.journal.saved.len()
+    }
+
+    /// Keeps the updates.
+    #[inline]
+    pub fn commit(mut self) {
+        self.journal = Journal::default();
+    }
+
+    /// Restores the arena to its state before the transaction.
+    #[inline]
+    pub fn rollback(self) {}
+
+    fn restore(&mut self) {
+        let Journal { entries, mut saved } = std::mem::take(&mut self.journal);
+
+        for (at, len) in entries.into_iter().rev() {
+            let bytes = saved.split_off(saved.len() - len as usize);
+            let restored = &mut self.arena[at as usize..][..len as usize];
+            restored.copy_from_slice(&bytes);
+
+            #[cfg(feature = "dirty")]
+            crate::dirty::mark(restored);
+        }
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+/// Journals the current contents of `overwritten` in every transaction whose arena contains it.
+#[inline]
+pub fn journal(overwritten: &[u8]) {
+    crate::hooks::each(overwritten, |journal: &mut Journal, at| {
+        journal.entries.push((at, overwritten.len() as crate::Ptr));
+        journal.saved.extend_from_slice(overwritten);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rollback_restores() {
+        let mut arena = [1, 2, 3, 4];
+
+        let mut transaction = Transaction::new(&mut arena);
+        transaction.apply(|arena| {
+            journal(&arena[1..3]);
+            arena[1..3].copy_from_slice(&[5, 6]);
+            journal(&arena[2..]);
+            arena[2..].copy_from_slice(&[7, 8]);
+        });
+        assert_eq!(transaction.journaled(), 4);
+        transaction.rollback();
+
+        assert_eq!(arena, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn commit_keeps() {
+        let mut arena = [1, 2];
+
+        let mut transaction = Transaction::new(&mut arena);
+        transaction.apply(|arena| {
+            journal(&arena[..1]);
+            arena[0] = 3;
+        });
+        transaction.commit();
+
+        assert_eq!(arena, [3, 2]);
+    }
+
+    #[test]
+    fn only_inside_apply() {
+        let mut arena = [1, 2];
+        let outside = [0; 2];
+
+        let mut transaction = Transaction::new(&mut arena);
+        transaction.apply(|_| journal(&outside));
+        journal(&outside);
+
+        assert_eq!(transaction.journaled(), 0);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::prelude::SliceExt;
+use crate::traits::{LiteralInnerType, Write, WriteTypeInfo};
+use crate::DynamicEnumWrapper;
+
+/// Writes a [DynamicEnumWrapper] with room for variants larger than the initial one. The
+/// footprint is the larger of `footprint` and the size of the initial variant.
+pub struct DynamicEnumWriter<'w, E> {
+    writer: &'w dyn WriteTypeInfo<E>,
+    footprint: crate::Ptr,
+}
+
+impl<'w, E> DynamicEnumWriter<'w, E> {
+    pub fn new(writer: &'w dyn WriteTypeInfo<E>, footprint: crate::Ptr) -> Self {
+        DynamicEnumWriter {
+            footprint: footprint.max(writer.result_size()),
+            writer,
+        }
+    }
+}
+
+impl<'a, 'w, E> WriteTypeInfo<DynamicEnumWrapper<'a, E>> for DynamicEnumWriter<'w, E> {
+    #[inline]
+    fn imprint(&self, arena: &mut [u8]) -> crate::Result<()> {
+        let (footprint, arena) = arena.noser_split(crate::Ptr::SIZE as crate::Ptr)?;
+        let (arena, _) = arena.noser_split(self.footprint)?;
+        let (inner, padding) = arena.noser_split(self.writer.result_size())?;
+
+        crate::Ptr::write(footprint, self.footprint);
+        self.writer.imprint(inner)?;
+
+        for byte in padding {
+            *byte = 0;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn result_size(&self) -> crate::Ptr {
+        crate::Ptr::SIZE as crate::Ptr + self.footprint
+    }
+}
@@ -1,5 +1,5 @@
-use std::iter::{Cloned, Repeat, Take};
-use std::slice::Iter;
+use core::iter::{Cloned, Repeat, Take};
+use core::slice::Iter;
 
 use crate::prelude::SliceExt;
 use crate::traits::{DefaultWriter, LiteralInnerType, Write, WriteTypeInfo};
@@ -63,7 +63,7 @@ pub type WithCapacity<'a, T> = ListWriter<TakeExactly<Take<Repeat<&'a dyn WriteT
 
 impl<'a, T> WithCapacity<'a, T> {
     pub fn repeat(writer: &'a dyn WriteTypeInfo<T>, capacity: crate::Ptr) -> Self {
-        ListWriter::new(std::iter::repeat(writer).take_exactly(capacity as usize))
+        ListWriter::new(core::iter::repeat(writer).take_exactly(capacity as usize))
     }
 
     pub fn with_capacity(capacity: crate::Ptr) -> Self
@@ -1,8 +1,13 @@
-use crate::traits::{DefaultWriter, LiteralInnerType, WriteTypeInfo};
+use crate::traits::{DefaultWriter, LiteralInnerType, Write, WriteTypeInfo};
 use crate::Literal;
 
 pub struct LiteralWriter;
 
+/// Imprints a literal holding `val`, see [Literal::with_value].
+pub struct LiteralValueWriter<T> {
+    val: T,
+}
+
 impl<'a, T> WriteTypeInfo<Literal<'a, T>> for LiteralWriter
 where
     T: LiteralInnerType,
@@ -18,6 +23,34 @@ where
     }
 }
 
+impl<'a, T> WriteTypeInfo<Literal<'a, T>> for LiteralValueWriter<T>
+where
+    T: LiteralInnerType + Write + Copy,
+{
+    #[inline]
+    fn imprint(&self, arena: &mut [u8]) -> crate::Result<()> {
+        T::imprint(arena)?;
+        T::write(arena, self.val);
+        Ok(())
+    }
+
+    #[inline]
+    fn result_size(&self) -> crate::Ptr {
+        T::SIZE as crate::Ptr
+    }
+}
+
+impl<T> Literal<'_, T>
+where
+    T: LiteralInnerType + Write + Copy,
+{
+    /// A writer imprinting a literal that already holds `val`.
+    #[inline]
+    pub fn with_value(val: T) -> LiteralValueWriter<T> {
+        LiteralValueWriter { val }
+    }
+}
+
 static WRITE_LITERAL_TYPE: LiteralWriter = LiteralWriter {};
 
 impl<'a, T> DefaultWriter for Literal<'a, T>
@@ -30,3 +63,26 @@ where
         &WRITE_LITERAL_TYPE
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Build;
+    use crate::List;
+
+    #[test]
+    fn with_value() {
+        let mut arena = Literal::<u32>::with_value(7).create_buffer().unwrap();
+        assert_eq!(arena, [7, 0, 0, 0]);
+        assert_eq!(Literal::<u32>::create(&mut arena).unwrap().read(), 7);
+
+        let arena = List::<Literal<i16>>::from(&[
+            &Literal::<i16>::with_value(-1),
+            Literal::<i16>::writer(),
+            &Literal::<i16>::with_value(3),
+        ])
+        .create_buffer()
+        .unwrap();
+        assert_eq!(arena, [3, 0, 0, 0, 255, 255, 0, 0, 3, 0]);
+    }
+}
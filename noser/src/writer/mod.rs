@@ -1,3 +1,4 @@
+pub mod enum_wrapper;
 pub mod list;
 pub mod literal;
 pub mod slice;
@@ -0,0 +1,53 @@
+#![cfg(feature = "alloc")]
+
+use noser::datagram::{DatagramPacker, Dispatcher};
+use noser::message::Message;
+use noser::{get, List, Literal};
+
+const INPUT: u16 = 1;
+const ACK: u16 = 2;
+
+type Numbers = List<'static, Literal<'static, u16>>;
+
+#[test]
+fn pack_datagrams() {
+    let mut message = Message::<Numbers>::new(&List::with_capacity(250)).unwrap();
+    let mut packer = DatagramPacker::new(1200);
+    let mut datagrams = vec![];
+    for frame in 0..4 {
+        let numbers = message.get_mut();
+        let mut first = get!(numbers[0]);
+        first.write(frame);
+
+        if !packer.push_message(INPUT, &message).unwrap() {
+            datagrams.push(packer.finish());
+            assert!(packer.push_message(INPUT, &message).unwrap());
+        }
+        packer
+            .imprint(ACK, &Literal::<u32>::with_value(u32::from(frame)))
+            .unwrap();
+    }
+    datagrams.push(packer.finish());
+    assert_eq!(datagrams.len(), 2);
+
+    let (mut frames, mut acks) = (vec![], vec![]);
+    let mut dispatcher = Dispatcher::new();
+    dispatcher
+        .on::<Numbers, _>(INPUT, |numbers| {
+            assert_eq!(numbers.capacity(), 250);
+            frames.push(numbers.borrow(0).read());
+            Ok(())
+        })
+        .on::<Literal<'static, u32>, _>(ACK, |ack| {
+            acks.push(ack.read());
+            Ok(())
+        });
+    for datagram in &mut datagrams {
+        assert!(datagram.len() <= 1200);
+        assert_eq!(dispatcher.dispatch(datagram).unwrap(), 4);
+    }
+    drop(dispatcher);
+
+    assert_eq!(frames, [0, 1, 2, 3]);
+    assert_eq!(acks, [0, 1, 2, 3]);
+}
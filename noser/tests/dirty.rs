@@ -0,0 +1,67 @@
+#![cfg(feature = "dirty")]
+#![allow(clippy::single_range_in_vec_init)]
+
+use noser::dirty::{mark, Dirty};
+use noser::message::Message;
+use noser::traits::*;
+use noser::{get, List, Literal};
+
+type Numbers = List<'static, Literal<'static, u16>>;
+
+#[test]
+fn per_client_deltas() {
+    let mut message = Message::<Numbers>::new(&List::with_capacity(4)).unwrap();
+    let acked_a = message.clone();
+    let (mut a, mut b, mut tick) = (Dirty::new(), Dirty::new(), Dirty::new());
+
+    message.track(&mut tick, |numbers| {
+        let mut second = get!(numbers[1]);
+        second.write(5);
+    });
+    assert_eq!(tick.ranges(), [6..8]);
+
+    a.merge(&tick);
+    b.merge(&tick);
+    tick.clear_dirty();
+
+    let acked_b = message.clone();
+    b.clear_dirty();
+
+    message.track(&mut tick, |numbers| {
+        let mut last = get!(numbers[3]);
+        last.write(7);
+        assert_eq!(numbers.borrow(2).read(), 0);
+    });
+    a.merge(&tick);
+    b.merge(&tick);
+
+    assert_eq!(a.ranges(), [6..8, 10..12]);
+    assert_eq!(b.ranges(), [10..12]);
+
+    let patch = a.patch(message.as_bytes()).unwrap();
+    assert_eq!(acked_a.patch(&patch).unwrap(), message);
+
+    let patch = b.patch(message.as_bytes()).unwrap();
+    assert_eq!(patch.splices().len(), 1);
+    assert_eq!(acked_b.patch(&patch).unwrap(), message);
+}
+
+#[test]
+fn slices_are_marked_by_hand() {
+    let mut arena = vec![2, 0, 0, 0, 7, 8];
+    let mut dirty = Dirty::new();
+
+    {
+        let bytes = <&mut [u8]>::create(&mut arena).unwrap();
+        bytes[0] = 1;
+    }
+
+    dirty.track(&mut arena, |arena| {
+        let bytes = <&mut [u8]>::create(arena).unwrap();
+        bytes[1] = 9;
+        mark(&bytes[1..]);
+    });
+
+    assert_eq!(dirty.ranges(), [5..6]);
+    assert_eq!(arena, [2, 0, 0, 0, 1, 9]);
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "alloc")]
+
+use noser::pool::Pool;
+use noser::traits::*;
+use noser::{get, List, Literal};
+
+#[test]
+fn pooled_buffers() {
+    let writer = List::<Literal<u16>>::with_capacity(8);
+    let mut pool = Pool::with_buffers(2, 64);
+    let mut seen = vec![];
+    for tick in 0..8 {
+        let mut arena = writer.create_buffer_in(&mut pool).unwrap();
+        let numbers = List::<Literal<u16>>::create(&mut arena).unwrap();
+        assert_eq!(numbers.capacity(), 8);
+        assert_eq!(numbers.borrow(7).read(), 0);
+
+        let mut last = get!(numbers[7]);
+        last.write(tick);
+
+        seen.push(arena.as_ptr());
+        pool.recycle(arena);
+    }
+
+    assert_eq!(pool.len(), 2);
+    assert!(seen.iter().all(|at| *at == seen[0]));
+}
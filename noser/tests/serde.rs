@@ -0,0 +1,99 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use noser::serde::{from_bytes, to_bytes};
+use noser::traits::*;
+use noser::{get, List, Literal};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Direction {
+    North,
+    East,
+    West,
+    South,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Action<'a> {
+    Move(Direction),
+    Aim { x: f32, y: f32 },
+    Say(&'a str),
+    Jump,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Proto<'a> {
+    frame: u8,
+    #[serde(borrow)]
+    actions: Vec<Action<'a>>,
+    ack: Option<u64>,
+}
+
+#[test]
+fn round_trip() {
+    let proto = Proto {
+        frame: 3,
+        actions: vec![
+            Action::Move(Direction::West),
+            Action::Aim { x: 0.5, y: -1.0 },
+            Action::Say("hi"),
+            Action::Jump,
+        ],
+        ack: Some(12),
+    };
+
+    let bytes = to_bytes(&proto).unwrap();
+    assert_eq!(from_bytes::<Proto>(&bytes).unwrap(), proto);
+}
+
+#[test]
+fn same_layout_as_list() {
+    let mut arena = to_bytes(&vec![vec![1u16, 2], vec![], vec![3]]).unwrap();
+
+    {
+        let owned = List::<List<'_, Literal<'_, u16>>>::create(&mut arena).unwrap();
+        assert_eq!(owned.capacity(), 3);
+        assert_eq!(owned.borrow(1).capacity(), 0);
+        assert_eq!(owned.borrow(2).borrow(0).read(), 3);
+
+        let mut item = get! { owned[0][1] };
+        item.write(20);
+    }
+
+    assert_eq!(
+        from_bytes::<Vec<Vec<u16>>>(&arena).unwrap(),
+        vec![vec![1, 20], vec![], vec![3]]
+    );
+}
+
+#[test]
+fn same_layout_as_byte_slice() {
+    let mut arena = to_bytes("こんにちは").unwrap();
+    let bytes = <&[u8]>::create(&mut arena).unwrap();
+
+    assert_eq!(std::str::from_utf8(bytes), Ok("こんにちは"));
+}
+
+#[test]
+fn maps_are_lists_of_pairs() {
+    let mut map = BTreeMap::new();
+    map.insert(1u8, 'a');
+    map.insert(2u8, 'b');
+
+    let bytes = to_bytes(&map).unwrap();
+
+    assert_eq!(bytes.len(), 4 + 2 * (1 + 4));
+    assert_eq!(from_bytes::<BTreeMap<u8, char>>(&bytes).unwrap(), map);
+}
+
+#[test]
+fn malformed_input() {
+    assert!(from_bytes::<bool>(&[2]).is_err());
+    assert!(from_bytes::<Option<u8>>(&[2, 0]).is_err());
+    assert!(from_bytes::<Direction>(&[4]).is_err());
+    assert!(from_bytes::<&str>(&[2, 0, 0, 0, 0xff, 0xff]).is_err());
+    assert!(from_bytes::<Vec<u64>>(&[0xff, 0xff, 0xff, 0xff, 0]).is_err());
+}
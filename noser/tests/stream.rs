@@ -0,0 +1,38 @@
+#![cfg(feature = "std")]
+
+use noser::message::Message;
+use noser::stream::{FrameReader, FrameWriter};
+use noser::{get, List, Literal};
+
+type Numbers = List<'static, Literal<'static, u16>>;
+
+#[test]
+fn replay_files() {
+    let mut message = Message::<Numbers>::new(&List::with_capacity(2)).unwrap();
+
+    let mut file = vec![];
+    {
+        let mut writer = FrameWriter::new(&mut file).with_batching(256);
+        for frame in 1..=3 {
+            let numbers = message.get_mut();
+            let mut first = get!(numbers[0]);
+            first.write(frame);
+            writer.write_message(&message).unwrap();
+        }
+        writer
+            .imprint(&List::<Literal<u16>>::with_capacity(0))
+            .unwrap();
+        assert!(writer.pending() > 0);
+        writer.flush().unwrap();
+    }
+
+    let mut reader = FrameReader::<_, Numbers>::new(std::io::Cursor::new(file));
+    let mut replayed = vec![];
+    while let Some(numbers) = reader.next_message().unwrap() {
+        replayed.push(match numbers.capacity() {
+            0 => None,
+            _ => Some(numbers.borrow(0).read()),
+        });
+    }
+    assert_eq!(replayed, [Some(1), Some(2), Some(3), None]);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "transaction")]
+#![allow(clippy::single_range_in_vec_init)]
+
+use noser::message::Message;
+use noser::traits::*;
+use noser::transaction::Transaction;
+use noser::{get, List, Literal, NoserError};
+
+type Numbers = List<'static, Literal<'static, u16>>;
+
+fn update(numbers: List<Literal<u16>>, updates: &[(u32, u16)]) -> noser::Result<()> {
+    for (idx, val) in updates {
+        if *idx >= numbers.capacity() {
+            return Err(NoserError::Malformed);
+        }
+
+        let mut number = get!(numbers[*idx]);
+        number.write(*val);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn all_or_nothing() {
+    let mut message = Message::<Numbers>::new(&List::with_capacity(3)).unwrap();
+
+    message
+        .transaction(|numbers| update(numbers, &[(0, 1), (2, 3)]))
+        .unwrap();
+    assert_eq!(message.as_bytes(), [3, 0, 0, 0, 1, 0, 0, 0, 3, 0]);
+
+    let result = message.transaction(|numbers| update(numbers, &[(1, 7), (0, 7), (3, 7)]));
+    assert!(result.is_err());
+    assert_eq!(message.as_bytes(), [3, 0, 0, 0, 1, 0, 0, 0, 3, 0]);
+}
+
+#[test]
+fn dropped_transactions_roll_back() {
+    let mut arena = vec![1, 0, 0, 0, 5, 0];
+
+    {
+        let mut transaction = Transaction::new(&mut arena);
+        transaction.apply(|arena| {
+            let numbers = List::<Literal<u16>>::create(arena).unwrap();
+            let mut first = get!(numbers[0]);
+            first.write(6);
+            first.write(7);
+        });
+        assert_eq!(transaction.journaled(), 4);
+    }
+
+    assert_eq!(arena, [1, 0, 0, 0, 5, 0]);
+}
+
+#[test]
+#[cfg(feature = "dirty")]
+fn rollbacks_are_dirty() {
+    use noser::dirty::Dirty;
+
+    let mut arena = vec![2, 0, 0, 0, 0, 0, 4, 0];
+    let mut dirty = Dirty::new();
+
+    dirty.track(&mut arena, |arena| {
+        let mut transaction = Transaction::new(arena);
+        transaction.apply(|arena| {
+            let numbers = List::<Literal<u16>>::create(arena).unwrap();
+            let mut first = get!(numbers[0]);
+            first.write(9);
+        });
+    });
+
+    assert_eq!(arena, [2, 0, 0, 0, 0, 0, 4, 0]);
+    assert_eq!(dirty.ranges(), [4..6]);
+}
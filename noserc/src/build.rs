@@ -53,11 +53,14 @@ pub(crate) fn struct_derive(mut input: DeriveInput, data: &DataStruct) -> crate:
     );
 
     let arena_generics = options.arena_generics(&input.generics);
+    let reborrow = reborrow_impl(&name, &input.generics, &arena_generics);
 
     let (impl_generics, ty_generics, where_clause) =
         crate::split_for_impl_add(&mut input.generics, &arena_generics);
 
     Ok(quote! {
+        #reborrow
+
         unsafe impl #impl_generics ::noser::traits::Build <#arena_generics> for #name #ty_generics #where_clause {
             #[inline]
             fn build<'_a>(arena: &'_a mut [u8]) -> ::noser::Result<(&'_a mut [u8], Self)>
@@ -81,15 +84,16 @@ pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::Der
     let options = crate::Options::from(&input.attrs)?;
 
     let arena_generics = options.arena_generics(&input.generics);
+    let reborrow = reborrow_impl(&name, &input.generics, &arena_generics);
 
     let (impl_generics, ty_generics, where_clause) =
         crate::split_for_impl_add(&mut input.generics, &arena_generics);
 
-    let num_variants = data.variants.len();
-    let variant_bytes = ((num_variants as f64).log2() / 8.0).ceil().max(0.) as usize;
+    let tags = crate::Tags::from(&input.attrs, data)?;
+    let variant_bytes = tags.bytes;
 
-    let variants = 0..num_variants as u64;
-    let variants2 = 0..num_variants as u64;
+    let variants = tags.values.iter();
+    let variants2 = tags.values.iter();
 
     let builders = data.variants.iter().map(|v| {
         let ident = &v.ident;
@@ -129,6 +133,8 @@ pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::Der
     });
 
     Ok(quote! {
+        #reborrow
+
         unsafe impl #impl_generics ::noser::traits::Build <#arena_generics> for #name #ty_generics #where_clause {
             #[inline]
             fn build<'_a>(arena: &'_a mut [u8]) -> ::noser::Result<(&'_a mut [u8], Self)>
@@ -159,3 +165,57 @@ pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::Der
         }
     })
 }
+
+/// `Reborrow` for the type with a `'static` arena, type parameters are reborrowed as well.
+fn reborrow_impl(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    arena: &syn::LifetimeDef,
+) -> proc_macro2::TokenStream {
+    let view: syn::Lifetime = syn::parse_quote!('noser_view);
+
+    let mut params = vec![quote!(#view)];
+    let mut static_args = vec![];
+    let mut view_args = vec![];
+    let mut predicates = vec![];
+
+    for param in &generics.params {
+        match param {
+            syn::GenericParam::Lifetime(def) if def.lifetime == arena.lifetime => {
+                static_args.push(quote!('static));
+                view_args.push(quote!(#view));
+            }
+            syn::GenericParam::Lifetime(def) => {
+                let lifetime = &def.lifetime;
+                params.push(quote!(#def));
+                static_args.push(quote!(#lifetime));
+                view_args.push(quote!(#lifetime));
+            }
+            syn::GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                params.push(quote!(#ident));
+                static_args.push(quote!(#ident));
+                view_args.push(quote!(<#ident as ::noser::traits::Reborrow<#view>>::View));
+                predicates.push(quote!(#ident: ::noser::traits::Reborrow<#view>));
+            }
+            syn::GenericParam::Const(c) => {
+                let (ident, ty) = (&c.ident, &c.ty);
+                params.push(quote!(const #ident: #ty));
+                static_args.push(quote!(#ident));
+                view_args.push(quote!(#ident));
+            }
+        }
+    }
+
+    let view_ty = quote!(#name<#(#view_args),*>);
+
+    quote! {
+        impl<#(#params),*> ::noser::traits::Reborrow<#view> for #name<#(#static_args),*>
+        where
+            #(#predicates,)*
+            #view_ty: ::noser::traits::Build<#view>
+        {
+            type View = #view_ty;
+        }
+    }
+}
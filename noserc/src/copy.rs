@@ -0,0 +1,103 @@
+use quote::quote;
+use syn::{parse_quote, spanned::Spanned, DeriveInput};
+
+pub(crate) fn derive(mut input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    for type_param in input.generics.type_params_mut() {
+        type_param
+            .bounds
+            .push(parse_quote!(::noser::traits::CopyInto));
+    }
+
+    let (size, copy) = match &input.data {
+        syn::Data::Struct(data) => (size(&data.fields), copy(&data.fields)),
+        syn::Data::Enum(data) => {
+            let tags = crate::Tags::from(&input.attrs, data)?;
+            let variant_bytes = tags.bytes;
+            let values = tags.values.iter();
+            let values2 = tags.values.iter();
+            let sizes = data.variants.iter().map(|v| size(&v.fields));
+            let copies = data.variants.iter().map(|v| copy(&v.fields));
+
+            (
+                quote! {
+                    let (tag, from) = from.noser_split_imut(#variant_bytes as ::noser::Ptr)?;
+                    size += #variant_bytes as ::noser::Ptr;
+
+                    let from = match ::noser::read_var_len_int(tag, #variant_bytes) {
+                        #(#values => { #sizes from })*
+                        _ => return Err(::noser::NoserError::Malformed),
+                    };
+                },
+                quote! {
+                    let (tag, from) = from.noser_split_imut(#variant_bytes as ::noser::Ptr)?;
+                    let (tag_to, to) = to.noser_split(#variant_bytes as ::noser::Ptr)?;
+                    tag_to.copy_from_slice(tag);
+
+                    let (from, to) = match ::noser::read_var_len_int(tag, #variant_bytes) {
+                        #(#values2 => { #copies (from, to) })*
+                        _ => return Err(::noser::NoserError::Malformed),
+                    };
+                },
+            )
+        }
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "'CopyInto' derive does not support this data type!",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::noser::traits::CopyInto for #name #ty_generics #where_clause {
+            #[inline]
+            fn copy_size(from: &[u8]) -> ::noser::Result<(::noser::Ptr, &[u8])> {
+                #[allow(unused_imports)]
+                use noser::prelude::SliceExt;
+
+                #[allow(unused_mut)]
+                let mut size: ::noser::Ptr = 0;
+                #size
+                Ok((size, from))
+            }
+
+            #[inline]
+            fn copy_into<'_f, '_t>(
+                from: &'_f [u8],
+                to: &'_t mut [u8],
+            ) -> ::noser::Result<(&'_f [u8], &'_t mut [u8])> {
+                #[allow(unused_imports)]
+                use noser::prelude::SliceExt;
+
+                #copy
+                Ok((from, to))
+            }
+        }
+    })
+}
+
+/// Statements adding the copy sizes of the fields to `size`, advancing `from` past them.
+fn size(fields: &syn::Fields) -> proc_macro2::TokenStream {
+    let types = fields.iter().map(|f| &f.ty);
+
+    quote! {
+        #(
+            let (field, from) = <#types as ::noser::traits::CopyInto>::copy_size(from)?;
+            size = size
+                .checked_add(field)
+                .ok_or(::noser::NoserError::IntegerOverflow)?;
+        )*
+    }
+}
+
+/// Statements copying the fields, advancing `from` and `to` past them.
+fn copy(fields: &syn::Fields) -> proc_macro2::TokenStream {
+    let types = fields.iter().map(|f| &f.ty);
+
+    quote! {
+        #(let (from, to) = <#types as ::noser::traits::CopyInto>::copy_into(from, to)?;)*
+    }
+}
@@ -0,0 +1,72 @@
+use quote::quote;
+use syn::{parse_quote, spanned::Spanned, DeriveInput};
+
+pub(crate) fn derive(mut input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    for type_param in input.generics.type_params_mut() {
+        type_param.bounds.push(parse_quote!(::noser::traits::Diff));
+    }
+
+    let diff = match &input.data {
+        syn::Data::Struct(data) => diff(&data.fields),
+        syn::Data::Enum(data) => {
+            let tags = crate::Tags::from(&input.attrs, data)?;
+            let variant_bytes = tags.bytes;
+            let values = tags.values.iter();
+            let diffs = data.variants.iter().map(|v| diff(&v.fields));
+
+            // A changed variant is recorded as a whole.
+            quote! {
+                let (old_tag, _) = old.noser_split_imut(#variant_bytes as ::noser::Ptr)?;
+                let (new_tag, _) = new.noser_split_imut(#variant_bytes as ::noser::Ptr)?;
+
+                if old_tag != new_tag {
+                    let old_size = <Self as ::noser::traits::size::Sizeable>::in_bounds(old)?;
+                    let new_size = <Self as ::noser::traits::size::Sizeable>::in_bounds(new)?;
+                    return patch.record(old, old_size, new, new_size);
+                }
+
+                let (old, new) = (&old[#variant_bytes..], &new[#variant_bytes..]);
+                let (old, new) = match ::noser::read_var_len_int(old_tag, #variant_bytes) {
+                    #(#values => { #diffs (old, new) })*
+                    _ => return Err(::noser::NoserError::Malformed),
+                };
+            }
+        }
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "'Diff' derive does not support this data type!",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::noser::traits::Diff for #name #ty_generics #where_clause {
+            #[inline]
+            #[allow(unused_variables)]
+            fn diff<'_o, '_n>(
+                old: &'_o [u8],
+                new: &'_n [u8],
+                patch: &mut ::noser::diff::Patch,
+            ) -> ::noser::Result<(&'_o [u8], &'_n [u8])> {
+                #[allow(unused_imports)]
+                use noser::prelude::SliceExt;
+
+                #diff
+                Ok((old, new))
+            }
+        }
+    })
+}
+
+/// Statements recording the changes of the fields, advancing `old` and `new` past them.
+fn diff(fields: &syn::Fields) -> proc_macro2::TokenStream {
+    let types = fields.iter().map(|f| &f.ty);
+
+    quote! {
+        #(let (old, new) = <#types as ::noser::traits::Diff>::diff(old, new, patch)?;)*
+    }
+}
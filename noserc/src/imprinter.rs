@@ -21,13 +21,12 @@ pub(crate) fn derive(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream
 pub(crate) fn struct_derive(mut input: DeriveInput, data: &DataStruct) -> crate::DeriveResult {
     use heck::ShoutySnakeCase;
 
+    let value_writer = struct_value_writer(&input, data);
+
     let name = input.ident;
+    let vis = input.vis;
 
-    for type_param in input.generics.type_params_mut() {
-        type_param
-            .bounds
-            .push(parse_quote!(::noser::traits::DefaultWriter));
-    }
+    default_writer_bounds(&mut input.generics, data.fields.iter());
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
@@ -45,7 +44,9 @@ pub(crate) fn struct_derive(mut input: DeriveInput, data: &DataStruct) -> crate:
     );
 
     Ok(quote! {
-        pub(crate) struct #imprinter_struct;
+        #value_writer
+
+        #vis struct #imprinter_struct;
 
         impl #impl_generics ::noser::traits::WriteTypeInfo<#name #ty_generics> for #imprinter_struct #where_clause {
             #[inline]
@@ -67,7 +68,7 @@ pub(crate) fn struct_derive(mut input: DeriveInput, data: &DataStruct) -> crate:
             }
         }
 
-        pub(crate) static #imprinter_static: #imprinter_struct = #imprinter_struct {};
+        #vis static #imprinter_static: #imprinter_struct = #imprinter_struct {};
 
         impl #impl_generics ::noser::traits::DefaultWriter for #name #ty_generics #where_clause {
             type Writer = #imprinter_struct;
@@ -81,20 +82,22 @@ pub(crate) fn struct_derive(mut input: DeriveInput, data: &DataStruct) -> crate:
 }
 
 pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::DeriveResult {
+    let value_writer = enum_value_writer(&input, data)?;
+
     let name = input.ident;
+    let vis = input.vis;
 
-    for type_param in input.generics.type_params_mut() {
-        type_param
-            .bounds
-            .push(parse_quote!(::noser::traits::DefaultWriter));
-    }
+    default_writer_bounds(
+        &mut input.generics,
+        data.variants.iter().flat_map(|v| v.fields.iter()),
+    );
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let num_variants = data.variants.len();
-    let variant_bytes = ((num_variants as f64).log2() / 8.0).ceil().max(0.) as usize;
+    let tags = crate::Tags::from(&input.attrs, data)?;
+    let variant_bytes = tags.bytes;
     let variant_bytes_repeat = std::iter::repeat(variant_bytes);
-    let variant_vals = 0..num_variants as u64;
+    let variant_vals = tags.values.iter();
 
     let variants = data.variants.iter().map(|v| &v.ident);
     let variants2 = data.variants.iter().map(|v| &v.ident);
@@ -130,7 +133,9 @@ pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::Der
     let imprinter_enum_repeat2 = std::iter::repeat(&imprinter_enum);
 
     Ok(quote! {
-        pub(crate) enum #imprinter_enum {
+        #value_writer
+
+        #vis enum #imprinter_enum {
             #(#variants,)*
         }
 
@@ -162,3 +167,205 @@ pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::Der
         }
     })
 }
+
+/// The `Imprint{Name}` writers use the default writer of every field, so they only exist where
+/// all fields have one. The bounds are higher-ranked, a plain bound on a type without generics
+/// that does not hold would be an error instead of disabling the impl.
+fn default_writer_bounds<'f>(
+    generics: &mut syn::Generics,
+    fields: impl Iterator<Item = &'f syn::Field>,
+) {
+    let where_clause = generics.make_where_clause();
+
+    for field in fields {
+        let ty = &field.ty;
+        where_clause
+            .predicates
+            .push(parse_quote!(for<'noser> #ty: ::noser::traits::DefaultWriter));
+    }
+}
+
+/// The generics of a `{Name}Writer` and of its impl, the `'writer` lifetime borrows the field
+/// writers. Writers without fields have no generics.
+fn writer_generics<'f>(
+    generics: &syn::Generics,
+    mut fields: impl Iterator<Item = &'f syn::Field>,
+) -> (syn::Generics, syn::Generics) {
+    if fields.next().is_none() {
+        return (syn::Generics::default(), generics.clone());
+    }
+
+    let mut generics = generics.clone();
+    generics.params.insert(0, parse_quote!('writer));
+    (generics.clone(), generics)
+}
+
+/// The field types of a `{Name}Writer`, a writer for each field of the value.
+fn writer_fields(fields: &syn::Fields, vis: &syn::Visibility) -> syn::Fields {
+    let mut fields = fields.clone();
+
+    for field in fields.iter_mut() {
+        let ty = &field.ty;
+        field.ty = parse_quote!(&'writer dyn ::noser::traits::WriteTypeInfo<#ty>);
+        field.vis = vis.clone();
+        field.attrs.clear();
+    }
+
+    fields
+}
+
+/// Names binding every field, and the pattern destructuring them.
+fn writer_bindings(fields: &syn::Fields) -> (Vec<syn::Ident>, proc_macro2::TokenStream) {
+    let bindings: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match &f.ident {
+            Some(ident) => ident.clone(),
+            None => syn::Ident::new(&format!("field_{}", i), proc_macro2::Span::call_site()),
+        })
+        .collect();
+
+    let names = bindings.iter();
+    let pattern = match fields {
+        syn::Fields::Named(_) => quote!({ #(#names),* }),
+        syn::Fields::Unnamed(_) => quote!(( #(#names),* )),
+        syn::Fields::Unit => quote!(),
+    };
+
+    (bindings, pattern)
+}
+
+/// `{Name}Writer`, holding a writer per field so that values can be imprinted with contents.
+fn struct_value_writer(input: &DeriveInput, data: &DataStruct) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let writer = syn::Ident::new(&format!("{}Writer", name), proc_macro2::Span::call_site());
+
+    let (writer_generics, writer_impl_generics) =
+        writer_generics(&input.generics, data.fields.iter());
+    let (_, writer_ty_generics, _) = writer_generics.split_for_impl();
+    let (impl_generics, _, where_clause) = writer_impl_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let fields = writer_fields(&data.fields, vis);
+    let semicolon = match fields {
+        syn::Fields::Named(_) => quote!(),
+        _ => quote!(;),
+    };
+
+    let (bindings, pattern) = writer_bindings(&data.fields);
+    let bindings1 = bindings.iter();
+    let bindings2 = bindings.iter();
+    let bindings3 = bindings.iter();
+
+    quote! {
+        #vis struct #writer #writer_generics #fields #semicolon
+
+        impl #impl_generics ::noser::traits::WriteTypeInfo<#name #ty_generics> for #writer #writer_ty_generics #where_clause {
+            #[inline]
+            #[allow(unused_variables)]
+            fn imprint(&self, arena: &mut [u8]) -> ::noser::Result<()> {
+                use noser::prelude::SliceExt;
+
+                let #writer #pattern = self;
+                #(
+                let (left, arena) = arena.noser_split(::noser::traits::WriteTypeInfo::result_size(*#bindings1))?;
+                ::noser::traits::WriteTypeInfo::imprint(*#bindings2, left)?;
+                )*
+                Ok(())
+            }
+
+            #[inline]
+            fn result_size(&self) -> ::noser::Ptr {
+                let #writer #pattern = self;
+                let mut size = 0;
+                #(size += ::noser::traits::WriteTypeInfo::result_size(*#bindings3);)*
+                size
+            }
+        }
+    }
+}
+
+/// `{Name}Writer`, a variant per variant of the enum holding a writer per field.
+fn enum_value_writer(input: &DeriveInput, data: &DataEnum) -> crate::DeriveResult {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let writer = syn::Ident::new(&format!("{}Writer", name), proc_macro2::Span::call_site());
+
+    let (writer_generics, writer_impl_generics) = writer_generics(
+        &input.generics,
+        data.variants.iter().flat_map(|v| v.fields.iter()),
+    );
+    let (_, writer_ty_generics, _) = writer_generics.split_for_impl();
+    let (impl_generics, _, where_clause) = writer_impl_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let tags = crate::Tags::from(&input.attrs, data)?;
+    let variant_bytes = tags.bytes;
+
+    let variants = data.variants.iter().map(|v| {
+        let ident = &v.ident;
+        let fields = writer_fields(&v.fields, &syn::Visibility::Inherited);
+        quote!(#ident #fields)
+    });
+
+    let imprinters = data.variants.iter().zip(&tags.values).map(|(v, tag)| {
+        let ident = &v.ident;
+        let (bindings, pattern) = writer_bindings(&v.fields);
+        let bindings1 = bindings.iter();
+        let bindings2 = bindings.iter();
+
+        quote! {
+            #writer::#ident #pattern => {
+                ::noser::write_var_len_int(left, #variant_bytes, #tag);
+                #(
+                let (left, arena) = arena.noser_split(::noser::traits::WriteTypeInfo::result_size(*#bindings1))?;
+                ::noser::traits::WriteTypeInfo::imprint(*#bindings2, left)?;
+                )*
+            }
+        }
+    });
+
+    let sizes = data.variants.iter().map(|v| {
+        let ident = &v.ident;
+        let (bindings, pattern) = writer_bindings(&v.fields);
+        let bindings = bindings.iter();
+
+        quote! {
+            #writer::#ident #pattern => {
+                #(size += ::noser::traits::WriteTypeInfo::result_size(*#bindings);)*
+            }
+        }
+    });
+
+    Ok(quote! {
+        #vis enum #writer #writer_generics {
+            #(#variants,)*
+        }
+
+        impl #impl_generics ::noser::traits::WriteTypeInfo<#name #ty_generics> for #writer #writer_ty_generics #where_clause {
+            #[inline]
+            #[allow(unused_variables)]
+            fn imprint(&self, arena: &mut [u8]) -> ::noser::Result<()> {
+                use noser::prelude::SliceExt;
+
+                let (left, arena) = arena.noser_split(#variant_bytes as ::noser::Ptr)?;
+
+                match self {
+                    #(#imprinters)*
+                }
+
+                Ok(())
+            }
+
+            #[inline]
+            fn result_size(&self) -> ::noser::Ptr {
+                let mut size = #variant_bytes as ::noser::Ptr;
+                match self {
+                    #(#sizes)*
+                };
+                size
+            }
+        }
+    })
+}
@@ -0,0 +1,256 @@
+use quote::quote;
+use syn::{parse_quote, spanned::Spanned, DataEnum, DataStruct, DeriveInput};
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    match &input.data {
+        syn::Data::Struct(data) => {
+            let data = data.clone();
+            struct_derive(input, &data)
+        }
+        syn::Data::Enum(data) => {
+            let data = data.clone();
+            enum_derive(input, &data)
+        }
+        _ => Err(syn::Error::new(
+            input.span(),
+            "'Json' derive does not support this data type!",
+        )),
+    }
+}
+
+fn field_idents(fields: &syn::Fields) -> Vec<syn::Ident> {
+    (0..fields.iter().count())
+        .map(|i| syn::Ident::new(&format!("field{}", i), proc_macro2::Span::call_site()))
+        .collect()
+}
+
+/// Statements reading every field in order from `arena` followed by an expression for the json
+/// value of the fields. Named fields become an object, a single unnamed field is transparent
+/// and several unnamed fields become an array.
+fn export(fields: &syn::Fields) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let idents = field_idents(fields);
+    let idents2 = idents.clone();
+    let types = fields.iter().map(|f| &f.ty);
+
+    let value = match fields {
+        syn::Fields::Unit => quote!(::noser::json::Value::Null),
+        syn::Fields::Named(named) => {
+            let names = named
+                .named
+                .iter()
+                .map(|f| f.ident.as_ref().unwrap().to_string());
+
+            quote! {
+                ::noser::json::Value::Object(::core::convert::From::from([#((::core::convert::From::from(#names), #idents2),)*]))
+            }
+        }
+        syn::Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => quote!(field0),
+        syn::Fields::Unnamed(_) => {
+            quote!(::noser::json::Value::Array(::core::convert::From::from([#(#idents2,)*])))
+        }
+    };
+
+    (
+        quote! {
+            #(let (#idents, arena) = <#types as ::noser::traits::Json>::to_json(arena)?;)*
+        },
+        value,
+    )
+}
+
+/// Statements that check the shape of `value` followed by an expression for the json value of
+/// each field.
+fn field_values(fields: &syn::Fields) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    match fields {
+        syn::Fields::Unit => (
+            quote! {
+                if *value != ::noser::json::Value::Null {
+                    return Err(::noser::json::JsonError::Mismatch("null").into());
+                }
+            },
+            vec![],
+        ),
+        syn::Fields::Named(named) => (
+            quote!(),
+            named
+                .named
+                .iter()
+                .map(|f| {
+                    let name = f.ident.as_ref().unwrap().to_string();
+                    quote!(value.field(#name)?)
+                })
+                .collect(),
+        ),
+        syn::Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+            (quote!(), vec![quote!(value)])
+        }
+        syn::Fields::Unnamed(unnamed) => {
+            let len = unnamed.unnamed.len();
+
+            (
+                quote!(let items = value.as_tuple(#len)?;),
+                (0..len).map(|i| quote!(&items[#i])).collect(),
+            )
+        }
+    }
+}
+
+fn size(fields: &syn::Fields) -> proc_macro2::TokenStream {
+    let (prelude, values) = field_values(fields);
+    let types = fields.iter().map(|f| &f.ty);
+
+    quote! {
+        #prelude
+        #(
+            size = size
+                .checked_add(<#types as ::noser::traits::Json>::json_size(#values)?)
+                .ok_or(::noser::NoserError::IntegerOverflow)?;
+        )*
+    }
+}
+
+fn imprint(fields: &syn::Fields) -> proc_macro2::TokenStream {
+    let (prelude, values) = field_values(fields);
+    let types = fields.iter().map(|f| &f.ty);
+
+    quote! {
+        #prelude
+        #(let arena = <#types as ::noser::traits::Json>::imprint_json(#values, arena)?;)*
+    }
+}
+
+pub(crate) fn struct_derive(mut input: DeriveInput, data: &DataStruct) -> crate::DeriveResult {
+    let name = input.ident;
+
+    for type_param in input.generics.type_params_mut() {
+        type_param.bounds.push(parse_quote!(::noser::traits::Json));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (export, value) = export(&data.fields);
+    let size = size(&data.fields);
+    let imprint = imprint(&data.fields);
+
+    Ok(quote! {
+        impl #impl_generics ::noser::traits::Json for #name #ty_generics #where_clause {
+            #[inline]
+            fn to_json(arena: &[u8]) -> ::noser::Result<(::noser::json::Value, &[u8])> {
+                #export
+                Ok((#value, arena))
+            }
+
+            #[inline]
+            fn json_size(value: &::noser::json::Value) -> ::noser::Result<::noser::Ptr> {
+                #[allow(unused_mut)]
+                let mut size: ::noser::Ptr = 0;
+                #size
+                Ok(size)
+            }
+
+            #[inline]
+            fn imprint_json<'_n>(value: &::noser::json::Value, arena: &'_n mut [u8]) -> ::noser::Result<&'_n mut [u8]> {
+                #imprint
+                Ok(arena)
+            }
+        }
+    })
+}
+
+pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::DeriveResult {
+    let name = input.ident;
+
+    for type_param in input.generics.type_params_mut() {
+        type_param.bounds.push(parse_quote!(::noser::traits::Json));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let tags = crate::Tags::from(&input.attrs, data)?;
+    let variant_bytes = tags.bytes;
+    let variant_bytes_repeat = std::iter::repeat(variant_bytes);
+
+    let variant_vals = tags.values.iter();
+    let variant_vals2 = tags.values.iter();
+
+    let names = data
+        .variants
+        .iter()
+        .map(|v| v.ident.to_string())
+        .collect::<Vec<_>>();
+    let names2 = names.clone();
+    let names3 = names.clone();
+
+    let exports = data.variants.iter().zip(&names).map(|(v, name)| {
+        let (export, value) = export(&v.fields);
+
+        let value = match v.fields {
+            syn::Fields::Unit => quote! {
+                ::noser::json::Value::String(::core::convert::From::from(#name))
+            },
+            _ => quote! {
+                ::noser::json::Value::Object(::core::convert::From::from([(::core::convert::From::from(#name), #value)]))
+            },
+        };
+
+        quote! {
+            #export
+            Ok((#value, arena))
+        }
+    });
+
+    let sizes = data.variants.iter().map(|v| size(&v.fields));
+    let imprints = data.variants.iter().map(|v| imprint(&v.fields));
+
+    Ok(quote! {
+        impl #impl_generics ::noser::traits::Json for #name #ty_generics #where_clause {
+            #[inline]
+            fn to_json(arena: &[u8]) -> ::noser::Result<(::noser::json::Value, &[u8])> {
+                use ::noser::prelude::SliceExt;
+
+                let (left, arena) = arena.noser_split_imut(#variant_bytes as ::noser::Ptr)?;
+
+                match ::noser::read_var_len_int(left, #variant_bytes) {
+                    #(#variant_vals => {
+                        #exports
+                    }),*
+                    _ => Err(::noser::NoserError::Malformed)
+                }
+            }
+
+            #[inline]
+            fn json_size(value: &::noser::json::Value) -> ::noser::Result<::noser::Ptr> {
+                // Unit variants may be written both as "Variant" and {"Variant": null}.
+                let (variant, value) = value.as_variant()?;
+                let value = value.unwrap_or(&::noser::json::Value::Null);
+
+                #[allow(unused_mut)]
+                let mut size = #variant_bytes as ::noser::Ptr;
+                match variant {
+                    #(#names2 => { #sizes },)*
+                    _ => return Err(::noser::json::JsonError::UnknownVariant(::core::convert::From::from(variant)).into()),
+                }
+                Ok(size)
+            }
+
+            #[inline]
+            fn imprint_json<'_n>(value: &::noser::json::Value, arena: &'_n mut [u8]) -> ::noser::Result<&'_n mut [u8]> {
+                use ::noser::prelude::SliceExt;
+
+                let (variant, value) = value.as_variant()?;
+                let value = value.unwrap_or(&::noser::json::Value::Null);
+
+                let (left, arena) = arena.noser_split(#variant_bytes as ::noser::Ptr)?;
+
+                match variant {
+                    #(#names3 => {
+                        ::noser::write_var_len_int(left, #variant_bytes_repeat, #variant_vals2);
+                        #imprints
+                        Ok(arena)
+                    },)*
+                    _ => Err(::noser::json::JsonError::UnknownVariant(::core::convert::From::from(variant)).into()),
+                }
+            }
+        }
+    })
+}
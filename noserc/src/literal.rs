@@ -0,0 +1,249 @@
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, DataEnum, DeriveInput};
+
+/// The single field of a newtype, as `self.0` or `self.name` would access it.
+fn newtype_field(input: &DeriveInput, derive: &str) -> syn::Result<(syn::Member, syn::Type)> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => unreachable!(),
+    };
+
+    let mut iter = fields.iter();
+    match (iter.next(), iter.next()) {
+        (Some(field), None) => {
+            let member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(syn::Index {
+                    index: 0,
+                    span: field.span(),
+                }),
+            };
+
+            Ok((member, field.ty.clone()))
+        }
+        _ => Err(syn::Error::new(
+            input.span(),
+            format!("'{}' derive needs a struct with exactly one field!", derive),
+        )),
+    }
+}
+
+/// The where clause of `generics`, requiring the newtype's field to read as itself. Fields read
+/// as something else, like `char` as `Option<char>`, fail on this bound instead of inside the
+/// generated code.
+fn reads_as_itself(generics: &syn::Generics, ty: &syn::Type) -> syn::WhereClause {
+    let mut generics = generics.clone();
+    let bound = quote_spanned! {ty.span()=>
+        #ty: ::noser::traits::Read<Output = #ty>
+    };
+
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse2(bound).expect("a where predicate"));
+    generics.make_where_clause().clone()
+}
+
+/// The enum's variants, which all have to be fieldless.
+fn unit_variants<'a>(data: &'a DataEnum, derive: &str) -> syn::Result<Vec<&'a syn::Ident>> {
+    data.variants
+        .iter()
+        .map(|v| match v.fields {
+            syn::Fields::Unit => Ok(&v.ident),
+            _ => Err(syn::Error::new(
+                v.span(),
+                format!("'{}' derive needs an enum without fields!", derive),
+            )),
+        })
+        .collect()
+}
+
+/// A newtype reads and writes like its field. The variants of a fieldless enum are written like
+/// the tags of a `Build` enum, and an unknown tag is read as `Err(tag)`.
+pub(crate) fn derive(input: DeriveInput) -> crate::DeriveResult {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    match &input.data {
+        syn::Data::Struct(_) => {
+            let (member, ty) = newtype_field(&input, "LiteralInnerType")?;
+            let read_where = reads_as_itself(&input.generics, &ty);
+
+            Ok(quote! {
+                impl #impl_generics ::noser::traits::LiteralInnerType for #name #ty_generics #where_clause {
+                    const SIZE: usize = <#ty as ::noser::traits::LiteralInnerType>::SIZE;
+
+                    #[inline]
+                    fn imprint(arena: &mut [u8]) -> ::noser::Result<()> {
+                        <#ty as ::noser::traits::LiteralInnerType>::imprint(arena)
+                    }
+
+                    #[inline]
+                    fn validate(arena: &[u8]) -> ::noser::Result<()> {
+                        <#ty as ::noser::traits::LiteralInnerType>::validate(arena)
+                    }
+                }
+
+                impl #impl_generics ::noser::traits::Write for #name #ty_generics #where_clause {
+                    #[inline]
+                    fn write(arena: &mut [u8], val: Self) {
+                        <#ty as ::noser::traits::Write>::write(arena, val.#member)
+                    }
+                }
+
+                impl #impl_generics ::noser::traits::Read for #name #ty_generics #read_where {
+                    type Output = Self;
+
+                    #[inline]
+                    fn read(arena: &[u8]) -> Self {
+                        Self { #member: <#ty as ::noser::traits::Read>::read(arena) }
+                    }
+                }
+
+                impl #impl_generics ::noser::traits::StructuralLiteral for #name #ty_generics #read_where {
+                    #[inline]
+                    fn compare(
+                        a: Self,
+                        b: Self,
+                        floats: ::noser::traits::Floats,
+                    ) -> ::core::option::Option<::core::cmp::Ordering> {
+                        <#ty as ::noser::traits::StructuralLiteral>::compare(a.#member, b.#member, floats)
+                    }
+                }
+            })
+        }
+        syn::Data::Enum(data) => {
+            let variants = unit_variants(data, "LiteralInnerType")?;
+            let variants2 = variants.clone();
+            let variants3 = variants.clone();
+            let tags = crate::Tags::from(&input.attrs, data)?;
+            let variant_bytes = tags.bytes;
+            let values = tags.values.iter();
+            let values2 = tags.values.iter();
+            let values3 = tags.values.iter();
+            let name_repeat = std::iter::repeat(name);
+            let name_repeat2 = name_repeat.clone();
+            let name_repeat3 = name_repeat.clone();
+
+            Ok(quote! {
+                impl #impl_generics ::noser::traits::LiteralInnerType for #name #ty_generics #where_clause {
+                    const SIZE: usize = #variant_bytes;
+
+                    #[inline]
+                    fn imprint(arena: &mut [u8]) -> ::noser::Result<()> {
+                        use ::noser::prelude::SliceExt;
+
+                        arena.noser_split(#variant_bytes as ::noser::Ptr)?;
+                        Ok(())
+                    }
+                }
+
+                impl #impl_generics ::noser::traits::Write for #name #ty_generics #where_clause {
+                    #[inline]
+                    fn write(arena: &mut [u8], val: Self) {
+                        let tag = match val {
+                            #(#name_repeat::#variants => #values,)*
+                        };
+                        ::noser::write_var_len_int(arena, #variant_bytes, tag)
+                    }
+                }
+
+                impl #impl_generics ::noser::traits::Read for #name #ty_generics #where_clause {
+                    /// The tag if it doesn't name a variant.
+                    type Output = ::core::result::Result<Self, u64>;
+
+                    #[inline]
+                    fn read(arena: &[u8]) -> ::core::result::Result<Self, u64> {
+                        match ::noser::read_var_len_int(arena, #variant_bytes) {
+                            #(#values2 => Ok(#name_repeat2::#variants2),)*
+                            tag => Err(tag),
+                        }
+                    }
+                }
+
+                // Variants are ordered by their tags, unknown tags included.
+                impl #impl_generics ::noser::traits::StructuralLiteral for #name #ty_generics #where_clause {
+                    #[inline]
+                    fn compare(
+                        a: ::core::result::Result<Self, u64>,
+                        b: ::core::result::Result<Self, u64>,
+                        _: ::noser::traits::Floats,
+                    ) -> ::core::option::Option<::core::cmp::Ordering> {
+                        let tag = |val: ::core::result::Result<Self, u64>| match val {
+                            #(Ok(#name_repeat3::#variants3) => #values3,)*
+                            Err(tag) => tag,
+                        };
+
+                        Some(tag(a).cmp(&tag(b)))
+                    }
+                }
+            })
+        }
+        _ => Err(syn::Error::new(
+            input.span(),
+            "'LiteralInnerType' derive does not support this data type!",
+        )),
+    }
+}
+
+/// A newtype is written like its field, a variant by its name like unit variants of a `Json`
+/// enum. Unknown tags are written as null.
+pub(crate) fn derive_json(input: DeriveInput) -> crate::DeriveResult {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    match &input.data {
+        syn::Data::Struct(_) => {
+            let (member, ty) = newtype_field(&input, "JsonLiteral")?;
+            let read_where = reads_as_itself(&input.generics, &ty);
+
+            Ok(quote! {
+                impl #impl_generics ::noser::traits::JsonLiteral for #name #ty_generics #read_where {
+                    #[inline]
+                    fn to_value(val: Self) -> ::noser::json::Value {
+                        <#ty as ::noser::traits::JsonLiteral>::to_value(val.#member)
+                    }
+
+                    #[inline]
+                    fn from_value(value: &::noser::json::Value) -> ::noser::Result<Self> {
+                        Ok(Self { #member: <#ty as ::noser::traits::JsonLiteral>::from_value(value)? })
+                    }
+                }
+            })
+        }
+        syn::Data::Enum(data) => {
+            let variants = unit_variants(data, "JsonLiteral")?;
+            let variants2 = variants.clone();
+            let names = variants.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+            let names2 = names.clone();
+            let name_repeat = std::iter::repeat(name);
+            let name_repeat2 = name_repeat.clone();
+
+            Ok(quote! {
+                impl #impl_generics ::noser::traits::JsonLiteral for #name #ty_generics #where_clause {
+                    #[inline]
+                    fn to_value(val: ::core::result::Result<Self, u64>) -> ::noser::json::Value {
+                        let name = match val {
+                            #(Ok(#name_repeat::#variants) => #names,)*
+                            Err(_) => return ::noser::json::Value::Null,
+                        };
+                        ::noser::json::Value::String(::core::convert::From::from(name))
+                    }
+
+                    #[inline]
+                    fn from_value(value: &::noser::json::Value) -> ::noser::Result<Self> {
+                        // Like enums deriving `Json`, "Variant" and {"Variant": null} are accepted.
+                        match value.as_variant()?.0 {
+                            #(#names2 => Ok(#name_repeat2::#variants2),)*
+                            variant => Err(::noser::json::JsonError::UnknownVariant(::core::convert::From::from(variant)).into()),
+                        }
+                    }
+                }
+            })
+        }
+        _ => Err(syn::Error::new(
+            input.span(),
+            "'JsonLiteral' derive does not support this data type!",
+        )),
+    }
+}
@@ -8,9 +8,14 @@ use quote::ToTokens;
 use syn::{parse_macro_input, parse_quote, DeriveInput};
 
 mod build;
+mod copy;
+mod diff;
 mod imprinter;
+mod json;
+mod literal;
 mod size;
 mod static_enum;
+mod structural;
 
 type DeriveResult = Result<TokenStream, syn::Error>;
 
@@ -18,6 +23,58 @@ fn unwrap(expanded: syn::Result<proc_macro2::TokenStream>) -> proc_macro::TokenS
     proc_macro::TokenStream::from(expanded.unwrap_or_else(|e| syn::Error::to_compile_error(&e)))
 }
 
+/// Enum variants are tagged in declaration order, `#[noserc(tag = N)]` on a variant and
+/// `#[noserc(tag_bytes = K)]` on the enum fix the wire format, and every derive honours them.
+///
+/// ```
+/// #[derive(noserc::Build)]
+/// #[noserc(tag_bytes = 1)]
+/// enum Action {
+///     #[noserc(tag = 4)]
+///     Jump,
+///     Duck, // 5
+/// }
+/// ```
+///
+/// Tags must be unique and fit the width:
+///
+/// ```compile_fail
+/// #[derive(noserc::Build)]
+/// enum Action {
+///     #[noserc(tag = 1)]
+///     Jump,
+///     #[noserc(tag = 0)]
+///     Duck,
+///     Crawl, // also 1
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[derive(noserc::Build)]
+/// #[noserc(tag_bytes = 1)]
+/// enum Action {
+///     #[noserc(tag = 256)]
+///     Jump,
+/// }
+/// ```
+///
+/// Each attribute only goes where it is described above:
+///
+/// ```compile_fail
+/// #[derive(noserc::Build)]
+/// #[noserc(tag = 1)]
+/// enum Action {
+///     Jump,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[derive(noserc::Build)]
+/// enum Action {
+///     #[noserc(tag_bytes = 2)]
+///     Jump,
+/// }
+/// ```
 #[proc_macro_derive(Build, attributes(noserc))]
 pub fn derive_build(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -25,7 +82,7 @@ pub fn derive_build(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     unwrap(build::derive(input))
 }
 
-#[proc_macro_derive(WriteTypeInfo)]
+#[proc_macro_derive(WriteTypeInfo, attributes(noserc))]
 pub fn derive_imprinter(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -33,29 +90,110 @@ pub fn derive_imprinter(input: proc_macro::TokenStream) -> proc_macro::TokenStre
     unwrap(imprinter::derive(input))
 }
 
-#[proc_macro_derive(StaticSizeable)]
+#[proc_macro_derive(Json, attributes(noserc))]
+pub fn derive_json(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    unwrap(json::derive(input))
+}
+
+/// Makes a newtype or a fieldless enum usable as a `Literal`, deriving `LiteralInnerType`, `Read`,
+/// `Write` and `StructuralLiteral`. A newtype reads as itself and needs a field that does too. An
+/// enum reads as `Result<Self, u64>`, unknown tags are returned as the error, and is laid out like
+/// the tag of an enum deriving `Build` with the same variants.
+///
+/// ```
+/// #[derive(Clone, Copy, noserc::LiteralInnerType)]
+/// struct PlayerId(u64);
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, noserc::LiteralInnerType)]
+/// #[noserc(tag_bytes = 1)]
+/// enum Direction {
+///     North,
+///     East,
+/// }
+///
+/// let arena = &mut [0; 1];
+/// <Direction as noser::traits::Write>::write(arena, Direction::East);
+/// assert_eq!(arena, &[1]);
+///
+/// arena[0] = 2;
+/// assert_eq!(<Direction as noser::traits::Read>::read(arena), Err(2));
+/// ```
+///
+/// `char` reads as `Option<char>`, so it can't be the field of a newtype:
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, noserc::LiteralInnerType)]
+/// struct Letter(char);
+/// ```
+#[proc_macro_derive(LiteralInnerType, attributes(noserc))]
+pub fn derive_literal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    unwrap(literal::derive(input))
+}
+
+#[proc_macro_derive(JsonLiteral, attributes(noserc))]
+pub fn derive_json_literal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    unwrap(literal::derive_json(input))
+}
+
+#[proc_macro_derive(StaticSizeable, attributes(noserc))]
 pub fn derive_size_static(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     unwrap(size::derive_static(input))
 }
 
-#[proc_macro_derive(DynamicSizeable)]
+#[proc_macro_derive(DynamicSizeable, attributes(noserc))]
 pub fn derive_size_dynamic(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     unwrap(size::derive_dynamic(input))
 }
 
-#[proc_macro_derive(StaticEnum)]
+#[proc_macro_derive(CopyInto, attributes(noserc))]
+pub fn derive_copy_into(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    unwrap(copy::derive(input))
+}
+
+#[proc_macro_derive(Structural, attributes(noserc))]
+pub fn derive_structural(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    unwrap(structural::derive(input))
+}
+
+#[proc_macro_derive(Diff, attributes(noserc))]
+pub fn derive_diff(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    unwrap(diff::derive(input))
+}
+
+#[proc_macro_derive(StaticEnum, attributes(noserc))]
 pub fn derive_static_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     unwrap(static_enum::derive(input))
 }
 
+#[proc_macro_derive(DynamicEnum, attributes(noserc))]
+pub fn derive_dynamic_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    unwrap(static_enum::derive_dynamic(input))
+}
+
 struct Options {
     arena: Option<syn::LifetimeDef>,
+    tag: Option<syn::LitInt>,
+    tag_bytes: Option<syn::LitInt>,
 }
 
 impl Options {
@@ -82,25 +220,34 @@ impl Options {
 
         let attrs = attrs?;
 
-        let arena: syn::Ident = parse_quote!(arena);
-
-        Ok(Options {
-            arena: match attrs
+        let value = |name: &str| {
+            attrs
                 .iter()
                 .filter_map(|nested| match nested {
-                    syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.ident == arena => {
-                        match &nv.lit {
-                            syn::Lit::Str(s) => Some(s.parse()),
-                            _ => None,
-                        }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.ident == name => {
+                        Some(&nv.lit)
                     }
                     _ => None,
                 })
                 .next()
-            {
-                Some(result) => Some(result?),
-                None => None,
+        };
+
+        let int = |name: &str| match value(name) {
+            Some(syn::Lit::Int(i)) => Ok(Some(i.clone())),
+            Some(lit) => Err(syn::Error::new(
+                syn::spanned::Spanned::span(lit),
+                format!("'{}' expects an integer", name),
+            )),
+            None => Ok(None),
+        };
+
+        Ok(Options {
+            arena: match value("arena") {
+                Some(syn::Lit::Str(s)) => Some(s.parse()?),
+                _ => None,
             },
+            tag: int("tag")?,
+            tag_bytes: int("tag_bytes")?,
         })
     }
 
@@ -122,6 +269,84 @@ impl Options {
     }
 }
 
+/// The tags of an enum's variants in declaration order, and the number of bytes they are written
+/// with. Variants are numbered like rust discriminants, counting up from the previous tag unless
+/// given with `#[noserc(tag = N)]`. The width is the fewest bytes fitting every tag, unless fixed
+/// with `#[noserc(tag_bytes = K)]` on the enum.
+pub(crate) struct Tags {
+    pub(crate) bytes: usize,
+    pub(crate) values: Vec<u64>,
+}
+
+impl Tags {
+    pub(crate) fn from(attrs: &[syn::Attribute], data: &syn::DataEnum) -> syn::Result<Tags> {
+        use syn::spanned::Spanned;
+
+        let mut values: Vec<u64> = vec![];
+        let mut next = Some(0);
+
+        let options = Options::from(attrs)?;
+        if let Some(tag) = options.tag {
+            return Err(syn::Error::new(
+                tag.span(),
+                "'tag' belongs on a variant, use 'tag_bytes' to set the width of the tags",
+            ));
+        }
+
+        for variant in &data.variants {
+            let variant_options = Options::from(&variant.attrs)?;
+            if let Some(bytes) = variant_options.tag_bytes {
+                return Err(syn::Error::new(
+                    bytes.span(),
+                    "'tag_bytes' belongs on the enum, it sets the width of every tag",
+                ));
+            }
+
+            let value = match variant_options.tag {
+                Some(tag) => tag.value(),
+                None => next.ok_or_else(|| {
+                    syn::Error::new(variant.span(), "tag overflows u64, give it explicitly")
+                })?,
+            };
+
+            if let Some(i) = values.iter().position(|v| *v == value) {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    format!(
+                        "tag {} is already used by '{}'",
+                        value, data.variants[i].ident
+                    ),
+                ));
+            }
+
+            values.push(value);
+            next = value.checked_add(1);
+        }
+
+        let max = values.iter().cloned().max().unwrap_or(0);
+        let needed = (0..8).take_while(|i| max >> (8 * i) != 0).count();
+
+        let bytes = match options.tag_bytes {
+            Some(bytes) if bytes.value() > 8 => {
+                return Err(syn::Error::new(
+                    bytes.span(),
+                    "'tag_bytes' can be at most 8",
+                ))
+            }
+            Some(bytes) if (bytes.value() as usize) < needed => {
+                return Err(syn::Error::new(
+                    bytes.span(),
+                    format!("tag {} does not fit in tag_bytes = {}", max, bytes.value()),
+                ))
+            }
+            Some(bytes) => bytes.value() as usize,
+            None => needed,
+        };
+
+        Ok(Tags { bytes, values })
+    }
+}
+
 fn validate_path(path: &syn::Path) -> bool {
     let noserc: syn::Ident = parse_quote!(noserc);
 
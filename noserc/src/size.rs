@@ -81,10 +81,10 @@ pub(crate) fn enum_derive_dynamic(input: DeriveInput, data: &DataEnum) -> crate:
     let name = input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let num_variants = data.variants.len();
-    let variant_bytes = ((num_variants as f64).log2() / 8.0).ceil().max(0.) as usize;
+    let tags = crate::Tags::from(&input.attrs, data)?;
+    let variant_bytes = tags.bytes;
 
-    let variants = 0..num_variants as u64;
+    let variants = tags.values.iter();
 
     let read_sizes = data.variants.iter().map(|v| {
         let types = v.fields.iter().map(|f| &f.ty);
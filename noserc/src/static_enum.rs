@@ -32,22 +32,13 @@ pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::Der
 
     let name = &input.ident;
 
-    let variant_enum_str = format!("{}Variant", name.to_string());
-    let variant_enum = syn::Ident::new(&variant_enum_str, proc_macro2::Span::call_site());
-
-    let variant_enum_repeat = std::iter::repeat(&variant_enum);
-    let variant_enum_repeat2 = variant_enum_repeat.clone();
-    let variant_enum_repeat3 = variant_enum_repeat.clone();
-    let variant_enum_repeat4 = variant_enum_repeat.clone();
+    let tags = crate::Tags::from(&input.attrs, data)?;
+    let variant_bytes = tags.bytes;
+    let (variant_enum, variant_enum_def) = variant_enum(&input.vis, name, data, &tags);
 
-    let num_variants = data.variants.len();
-    let variant_bytes = ((num_variants as f64).log2() / 8.0).ceil().max(0.) as usize;
-    let variant_vals = 0..num_variants as u64;
-    let variant_vals2 = 0..num_variants as u64;
+    let variant_enum_repeat3 = std::iter::repeat(&variant_enum);
+    let variant_enum_repeat4 = variant_enum_repeat3.clone();
 
-    let variants = data.variants.iter().map(|v| &v.ident);
-    let variants2 = data.variants.iter().map(|v| &v.ident);
-    let variants3 = data.variants.iter().map(|v| &v.ident);
     let variants4 = data.variants.iter().map(|v| &v.ident);
     let variants5 = data.variants.iter().map(|v| &v.ident);
 
@@ -101,33 +92,13 @@ pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::Der
     });
 
     Ok(quote! {
-        enum #variant_enum {
-            #(#variants,)*
-        }
-
-        impl ::noser::traits::Tagged for #variant_enum {
-            #[inline]
-            fn variant_tag(&self) -> u64 {
-                match self {
-                    #(#variant_enum_repeat::#variants2 => #variant_vals,)*
-                }
-            }
-
-            #[inline]
-            fn from_tag(tag: u64) -> ::noser::Result<Self> {
-                match tag {
-                    #(#variant_vals2 => Ok(#variant_enum_repeat2::#variants3),)*
-                    _ => Err(::noser::NoserError::Malformed)
-                }
-            }
-
-        }
+        #variant_enum_def
 
         impl #impl_generics ::noser::traits::StaticEnum<#arena_generics> for #name #ty_generics #where_clause {
             type VariantEnum = #variant_enum;
 
             const VARIANT_BYTES: usize = #variant_bytes;
-            const CONTENTS_SIZE: std::option::Option<usize> = None;
+            const CONTENTS_SIZE: ::core::option::Option<usize> = None;
 
             fn calculate_contents_size() -> usize {
                 let mut max_size = 0;
@@ -160,3 +131,86 @@ pub(crate) fn enum_derive(mut input: DeriveInput, data: &DataEnum) -> crate::Der
         }
     })
 }
+
+pub(crate) fn derive_dynamic(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    match &input.data {
+        syn::Data::Enum(data) => {
+            let data = data.clone();
+            enum_derive_dynamic(input, &data)
+        }
+        _ => Err(syn::Error::new(
+            input.span(),
+            "'DynamicEnum' derive does not support this data type!",
+        )),
+    }
+}
+
+pub(crate) fn enum_derive_dynamic(mut input: DeriveInput, data: &DataEnum) -> crate::DeriveResult {
+    let options = crate::Options::from(&input.attrs)?;
+    let arena_generics = options.arena_generics(&input.generics);
+
+    let (impl_generics, ty_generics, where_clause) =
+        crate::split_for_impl_add(&mut input.generics, &arena_generics);
+
+    let name = &input.ident;
+
+    let tags = crate::Tags::from(&input.attrs, data)?;
+    let variant_bytes = tags.bytes;
+    let (variant_enum, variant_enum_def) = variant_enum(&input.vis, name, data, &tags);
+
+    Ok(quote! {
+        #variant_enum_def
+
+        impl #impl_generics ::noser::traits::DynamicEnum<#arena_generics> for #name #ty_generics #where_clause {
+            type VariantEnum = #variant_enum;
+
+            const VARIANT_BYTES: usize = #variant_bytes;
+        }
+    })
+}
+
+/// A fieldless `{name}Variant` enum naming the variants, tagged like the enum itself.
+fn variant_enum(
+    vis: &syn::Visibility,
+    name: &syn::Ident,
+    data: &DataEnum,
+    tags: &crate::Tags,
+) -> (syn::Ident, proc_macro2::TokenStream) {
+    let variant_enum_str = format!("{}Variant", name.to_string());
+    let variant_enum = syn::Ident::new(&variant_enum_str, proc_macro2::Span::call_site());
+
+    let variant_enum_repeat = std::iter::repeat(&variant_enum);
+    let variant_enum_repeat2 = variant_enum_repeat.clone();
+
+    let variant_vals = tags.values.iter();
+    let variant_vals2 = tags.values.iter();
+
+    let variants = data.variants.iter().map(|v| &v.ident);
+    let variants2 = data.variants.iter().map(|v| &v.ident);
+    let variants3 = data.variants.iter().map(|v| &v.ident);
+
+    let tokens = quote! {
+        #vis enum #variant_enum {
+            #(#variants,)*
+        }
+
+        impl ::noser::traits::Tagged for #variant_enum {
+            #[inline]
+            fn variant_tag(&self) -> u64 {
+                match self {
+                    #(#variant_enum_repeat::#variants2 => #variant_vals,)*
+                }
+            }
+
+            #[inline]
+            fn from_tag(tag: u64) -> ::noser::Result<Self> {
+                match tag {
+                    #(#variant_vals2 => Ok(#variant_enum_repeat2::#variants3),)*
+                    _ => Err(::noser::NoserError::Malformed)
+                }
+            }
+        }
+    };
+
+    (variant_enum, tokens)
+}
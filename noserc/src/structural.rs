@@ -0,0 +1,113 @@
+use quote::quote;
+use syn::{parse_quote, spanned::Spanned, DeriveInput};
+
+pub(crate) fn derive(mut input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    for type_param in input.generics.type_params_mut() {
+        type_param
+            .bounds
+            .push(parse_quote!(::noser::traits::Structural));
+    }
+
+    let (compare, hash) = match &input.data {
+        syn::Data::Struct(data) => (compare(&data.fields), hash(&data.fields)),
+        syn::Data::Enum(data) => {
+            let tags = crate::Tags::from(&input.attrs, data)?;
+            let variant_bytes = tags.bytes;
+            let values = tags.values.iter();
+            let values2 = tags.values.iter();
+            let compares = data.variants.iter().map(|v| compare(&v.fields));
+            let hashes = data.variants.iter().map(|v| hash(&v.fields));
+
+            (
+                // Variants are ordered by their tags.
+                quote! {
+                    let (tag_a, a) = a.noser_split_imut(#variant_bytes as ::noser::Ptr)?;
+                    let (tag_b, b) = b.noser_split_imut(#variant_bytes as ::noser::Ptr)?;
+                    let tag_a = ::noser::read_var_len_int(tag_a, #variant_bytes);
+                    let tag_b = ::noser::read_var_len_int(tag_b, #variant_bytes);
+
+                    if tag_a != tag_b {
+                        return Ok((Some(tag_a.cmp(&tag_b)), a, b));
+                    }
+
+                    let (a, b) = match tag_a {
+                        #(#values => { #compares (a, b) })*
+                        _ => return Err(::noser::NoserError::Malformed),
+                    };
+                },
+                quote! {
+                    let (tag, arena) = arena.noser_split_imut(#variant_bytes as ::noser::Ptr)?;
+                    state.write(tag);
+
+                    let arena = match ::noser::read_var_len_int(tag, #variant_bytes) {
+                        #(#values2 => { #hashes arena })*
+                        _ => return Err(::noser::NoserError::Malformed),
+                    };
+                },
+            )
+        }
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "'Structural' derive does not support this data type!",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::noser::traits::Structural for #name #ty_generics #where_clause {
+            #[inline]
+            #[allow(unused_variables)]
+            fn compare<'_a, '_b>(
+                a: &'_a [u8],
+                b: &'_b [u8],
+                floats: ::noser::traits::Floats,
+            ) -> ::noser::Result<(Option<::core::cmp::Ordering>, &'_a [u8], &'_b [u8])> {
+                #[allow(unused_imports)]
+                use noser::prelude::SliceExt;
+
+                #compare
+                Ok((Some(::core::cmp::Ordering::Equal), a, b))
+            }
+
+            #[inline]
+            #[allow(unused_variables)]
+            fn hash<'_a, H: ::core::hash::Hasher>(
+                arena: &'_a [u8],
+                state: &mut H,
+            ) -> ::noser::Result<&'_a [u8]> {
+                #[allow(unused_imports)]
+                use noser::prelude::SliceExt;
+
+                #hash
+                Ok(arena)
+            }
+        }
+    })
+}
+
+/// Statements comparing the fields in order, returning at the first one that is not equal.
+fn compare(fields: &syn::Fields) -> proc_macro2::TokenStream {
+    let types = fields.iter().map(|f| &f.ty);
+
+    quote! {
+        #(
+            let (ordering, a, b) = <#types as ::noser::traits::Structural>::compare(a, b, floats)?;
+            if ordering != Some(::core::cmp::Ordering::Equal) {
+                return Ok((ordering, a, b));
+            }
+        )*
+    }
+}
+
+/// Statements hashing the fields, advancing `arena` past them.
+fn hash(fields: &syn::Fields) -> proc_macro2::TokenStream {
+    let types = fields.iter().map(|f| &f.ty);
+
+    quote! {
+        #(let arena = <#types as ::noser::traits::Structural>::hash(arena, state)?;)*
+    }
+}
@@ -0,0 +1,64 @@
+use noser::traits::{CopyInto, WriteTypeInfo};
+use noser::writer::enum_wrapper::DynamicEnumWriter;
+use noser::{DynamicEnumWrapper, List, Literal};
+use noserc::{Build, CopyInto, DynamicEnum, DynamicSizeable, WriteTypeInfo};
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, DynamicEnum, WriteTypeInfo, CopyInto)]
+enum Slot<'a> {
+    Empty,
+    Full(Literal<'a, u32>),
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, CopyInto)]
+struct Snapshot<'a> {
+    id: Literal<'a, u8>,
+    slot: DynamicEnumWrapper<'a, Slot<'a>>,
+    rows: List<'a, List<'a, Literal<'a, u8>>>,
+}
+
+#[test]
+fn nested_lists() {
+    let arena = vec![2, 0, 0, 0, 1, 0, 0, 0, 7, 2, 0, 0, 0, 8, 9, 0xff];
+
+    let (size, rest) = List::<List<Literal<u8>>>::copy_size(&arena).unwrap();
+    assert_eq!(size, 15);
+    assert_eq!(rest, [0xff]);
+
+    let mut target = vec![0; 16];
+    let (rest, unused) = List::<List<Literal<u8>>>::copy_into(&arena, &mut target).unwrap();
+    assert_eq!(rest, [0xff]);
+    assert_eq!(unused.len(), 1);
+    assert_eq!(target[..15], arena[..15]);
+
+    assert!(List::<List<Literal<u8>>>::copy_into(&arena, &mut [0; 14]).is_err());
+    assert!(List::<List<Literal<u8>>>::copy_size(&arena[..12]).is_err());
+}
+
+#[test]
+fn compacts_dynamic_enums() {
+    let slot = DynamicEnumWriter::new(&ImprintSlot::Empty, 16)
+        .create_buffer()
+        .unwrap();
+
+    let mut arena = vec![5];
+    arena.extend(slot);
+    arena.extend(&[1, 0, 0, 0, 0, 0, 0, 0]);
+
+    let copy = Snapshot::copy_to_vec(&arena).unwrap();
+    assert_eq!(copy, [5, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+
+    let mut full = vec![5];
+    full.extend(
+        DynamicEnumWriter::new(&ImprintSlot::Full, 0)
+            .create_buffer()
+            .unwrap(),
+    );
+    full.extend(&[0; 4]);
+    assert_eq!(Snapshot::copy_size(&full).unwrap().0, 14);
+
+    let mut unknown = copy.clone();
+    unknown[5] = 2;
+    assert!(Snapshot::copy_to_vec(&unknown).is_err());
+}
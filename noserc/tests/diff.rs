@@ -0,0 +1,190 @@
+use noser::builder::MessageBuilder;
+use noser::diff::{apply_patch, diff, Patch};
+use noser::{DynamicEnumWrapper, List, Literal};
+use noserc::{Build, Diff, DynamicEnum, DynamicSizeable};
+use proptest::prelude::*;
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, DynamicEnum, Diff)]
+enum Slot<'a> {
+    Empty,
+    Full(Literal<'a, u32>),
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Diff)]
+enum Kind<'a> {
+    Idle,
+    Moving(Literal<'a, i16>, Literal<'a, i16>),
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Diff)]
+struct Snapshot<'a> {
+    id: Literal<'a, u8>,
+    slot: DynamicEnumWrapper<'a, Slot<'a>>,
+    rows: List<'a, List<'a, Literal<'a, u16>>>,
+    kind: Kind<'a>,
+    name: &'a [u8],
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    id: u8,
+    slot: Option<u32>,
+    spare: u8,
+    rows: Vec<Vec<u16>>,
+    kind: Option<(i16, i16)>,
+    name: Vec<u8>,
+}
+
+impl Model {
+    fn encode(&self) -> Vec<u8> {
+        let mut builder = MessageBuilder::new();
+        builder.literal(self.id);
+
+        let footprint = 5 + u32::from(self.spare);
+        builder.literal(footprint);
+        let used = match self.slot {
+            Some(full) => builder.tag(1, 1).literal(full).len(),
+            None => builder.tag(1, 0).len(),
+        };
+        for _ in used..5 + footprint as usize {
+            builder.literal(0u8);
+        }
+
+        builder.list(|list| {
+            for row in &self.rows {
+                list.item(|b| {
+                    b.list(|list| {
+                        for value in row {
+                            list.item(|b| {
+                                b.literal(*value);
+                            });
+                        }
+                    });
+                });
+            }
+        });
+
+        match self.kind {
+            Some((x, y)) => builder.tag(1, 1).literal(x).literal(y),
+            None => builder.tag(1, 0),
+        };
+
+        builder.bytes(&self.name);
+        builder.finish().unwrap()
+    }
+}
+
+fn model() -> impl Strategy<Value = Model> {
+    (
+        any::<u8>(),
+        any::<Option<u32>>(),
+        0..4u8,
+        prop::collection::vec(prop::collection::vec(any::<u16>(), 0..4), 0..4),
+        any::<Option<(i16, i16)>>(),
+        prop::collection::vec(any::<u8>(), 0..8),
+    )
+        .prop_map(|(id, slot, spare, rows, kind, name)| Model {
+            id,
+            slot,
+            spare,
+            rows,
+            kind,
+            name,
+        })
+}
+
+/// A model sharing some fields with `old`, so patches have unchanged parts to skip.
+fn changed() -> impl Strategy<Value = (Model, Model)> {
+    (model(), model(), any::<[bool; 6]>()).prop_map(|(old, other, keep)| {
+        let new = Model {
+            id: if keep[0] { old.id } else { other.id },
+            slot: if keep[1] { old.slot } else { other.slot },
+            spare: if keep[2] { old.spare } else { other.spare },
+            rows: if keep[3] {
+                old.rows.clone()
+            } else {
+                other.rows
+            },
+            kind: if keep[4] { old.kind } else { other.kind },
+            name: if keep[5] {
+                old.name.clone()
+            } else {
+                other.name
+            },
+        };
+
+        (old, new)
+    })
+}
+
+proptest! {
+    #[test]
+    fn round_trip((old, new) in changed()) {
+        let (old, new) = (old.encode(), new.encode());
+
+        let patch = diff::<Snapshot>(&old, &new).unwrap();
+        prop_assert_eq!(apply_patch(&old, &patch).unwrap(), new);
+
+        let decoded = Patch::from_bytes(&patch.to_bytes().unwrap()).unwrap();
+        prop_assert_eq!(decoded, patch);
+    }
+
+    #[test]
+    fn equal_buffers(model in model()) {
+        let arena = model.encode();
+        prop_assert!(diff::<Snapshot>(&arena, &arena).unwrap().is_empty());
+    }
+}
+
+fn base() -> Model {
+    Model {
+        id: 1,
+        slot: None,
+        spare: 2,
+        rows: vec![vec![1, 2], vec![3]],
+        kind: Some((4, 5)),
+        name: b"abc".to_vec(),
+    }
+}
+
+#[test]
+fn only_changes_are_sent() {
+    let old = base();
+
+    let mut new = base();
+    new.rows[0][1] = 0x0702;
+    let patch = diff::<Snapshot>(&old.encode(), &new.encode()).unwrap();
+    assert_eq!(patch.splices().len(), 1);
+    assert_eq!(patch.splices()[0].inserted, [0x07]);
+
+    let mut new = base();
+    new.rows.push(vec![6; 3]);
+    let patch = diff::<Snapshot>(&old.encode(), &new.encode()).unwrap();
+    let inserted: usize = patch.splices().iter().map(|s| s.inserted.len()).sum();
+    assert_eq!(inserted, 1 + 4 + 3 * 2);
+
+    let mut new = base();
+    new.slot = Some(9);
+    let patch = diff::<Snapshot>(&old.encode(), &new.encode()).unwrap();
+    assert_eq!(patch.splices().len(), 1);
+    assert_eq!(patch.splices()[0].inserted, [1, 9]);
+}
+
+#[test]
+fn rejects_other_buffers() {
+    let old = base().encode();
+    let patch = diff::<Snapshot>(&old, &old).unwrap();
+
+    assert!(apply_patch(&old[1..], &patch).is_err());
+
+    let mut trailing = old.clone();
+    trailing.push(0);
+    assert!(diff::<Snapshot>(&old, &trailing).is_err());
+
+    let mut unknown = old.clone();
+    unknown[5] = 2;
+    assert!(diff::<Snapshot>(&unknown, &unknown).is_err());
+}
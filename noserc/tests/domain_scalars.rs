@@ -0,0 +1,37 @@
+use noser::json::{from_json, to_json, Value};
+use noser::traits::Build;
+use noser::{Fixed, List, Literal, Uuid};
+use noserc::{Build, DynamicSizeable, Json, Structural};
+use std::time::Duration;
+
+/// The derives parse types with syn 0.15, which predates const generic arguments.
+type Q16 = Fixed<i32, 16>;
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Json, Structural)]
+struct Telemetry<'a> {
+    id: Literal<'a, Uuid>,
+    uptime: Literal<'a, Duration>,
+    samples: List<'a, Literal<'a, Q16>>,
+}
+
+#[test]
+fn domain_scalars() {
+    let text = concat!(
+        r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8","#,
+        r#""uptime":{"secs":90,"nanos":500},"samples":[1.5,-0.25]}"#
+    );
+    let mut arena = from_json::<Telemetry>(&Value::parse(text).unwrap()).unwrap();
+    assert_eq!(arena.len(), 16 + 12 + 4 + 2 * 4);
+    assert_eq!(to_json::<Telemetry>(&arena).unwrap().to_string(), text);
+
+    let telemetry = Telemetry::create(&mut arena).unwrap();
+    assert_eq!(
+        telemetry.id.read().to_string(),
+        "67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+    assert_eq!(telemetry.uptime.read(), Some(Duration::new(90, 500)));
+
+    let sum = telemetry.samples.borrow(0).read() + telemetry.samples.borrow(1).read();
+    assert_eq!(sum * Q16::from_int(4).unwrap(), Q16::from_int(5).unwrap());
+}
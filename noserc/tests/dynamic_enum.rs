@@ -0,0 +1,90 @@
+use noser::traits::{Build, WriteTypeInfo};
+use noser::writer::enum_wrapper::DynamicEnumWriter;
+use noser::{DynamicEnumWrapper, List, Literal, NoserError};
+use noserc::{Build, DynamicEnum, DynamicSizeable, WriteTypeInfo};
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, DynamicEnum, WriteTypeInfo)]
+enum State<'a> {
+    Idle,
+    Running { since: Literal<'a, u64> },
+    Done(Literal<'a, u32>, Literal<'a, u32>),
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, DynamicEnum)]
+enum Inbox<'a> {
+    Closed,
+    Queued(List<'a, Literal<'a, u8>>),
+}
+
+#[test]
+fn reinterpret_in_place() {
+    let writer = DynamicEnumWriter::new(&ImprintState::Idle, 12);
+    let mut arena = writer.create_buffer().unwrap();
+    assert_eq!(arena, [12, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    {
+        let state = DynamicEnumWrapper::<State>::create(&mut arena).unwrap();
+        assert_eq!(state.footprint(), 12);
+        assert!(freyr::matches!(*state, State::Idle));
+
+        let mut state = state.reinterpret(&StateVariant::Running).ok().unwrap();
+        match &mut *state {
+            State::Running { since } => since.write(7),
+            _ => panic!("expected State::Running"),
+        }
+    }
+    assert_eq!(arena, [12, 0, 0, 0, 1, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    {
+        let state = DynamicEnumWrapper::<State>::create(&mut arena).unwrap();
+        let state = state.reinterpret(&StateVariant::Done).ok().unwrap();
+        match &*state {
+            State::Done(a, b) => assert_eq!((a.read(), b.read()), (0, 0)),
+            _ => panic!("expected State::Done"),
+        }
+    }
+    assert_eq!(arena, [12, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn reinterpret_does_not_fit() {
+    let writer = DynamicEnumWriter::new(&ImprintState::Running, 4);
+    assert_eq!(writer.result_size(), 13);
+    let writer = DynamicEnumWriter::new(&ImprintState::Done, 16);
+    assert_eq!(writer.result_size(), 20);
+
+    let mut arena = DynamicEnumWriter::new(&ImprintState::Idle, 4)
+        .create_buffer()
+        .unwrap();
+    let state = DynamicEnumWrapper::<State>::create(&mut arena).unwrap();
+
+    let (state, error) = state.reinterpret(&StateVariant::Running).err().unwrap();
+    assert!(freyr::matches!(error, NoserError::DoesNotFit(4)));
+    assert!(freyr::matches!(*state, State::Idle));
+}
+
+#[test]
+fn reinterpret_to_empty_list() {
+    let mut arena = vec![5, 0, 0, 0, 0, 0, 0, 0, 0];
+    {
+        let inbox = DynamicEnumWrapper::<Inbox>::create(&mut arena).unwrap();
+        let inbox = inbox.reinterpret(&InboxVariant::Queued).ok().unwrap();
+        match &*inbox {
+            Inbox::Queued(list) => assert_eq!(list.capacity(), 0),
+            _ => panic!("expected Inbox::Queued"),
+        }
+    }
+    assert_eq!(arena, [5, 0, 0, 0, 1, 0, 0, 0, 0]);
+
+    let mut arena = vec![4, 0, 0, 0, 0, 0, 0, 0];
+    let inbox = DynamicEnumWrapper::<Inbox>::create(&mut arena).unwrap();
+    assert!(inbox.reinterpret(&InboxVariant::Queued).is_err());
+}
+
+#[test]
+fn malformed_footprint() {
+    let mut arena = vec![9, 0, 0, 0, 0];
+    assert!(DynamicEnumWrapper::<State>::create(&mut arena).is_err());
+}
@@ -39,3 +39,109 @@ fn eh() {
     let owned = List::<Literal<u64>>::create(&mut arena).unwrap();
     owned.borrow(49);
 }
+
+#[allow(dead_code)]
+#[derive(WriteTypeInfo)]
+enum Direction {
+    North,
+    South,
+}
+
+#[allow(dead_code)]
+#[derive(WriteTypeInfo)]
+enum Action<'a> {
+    Move(Direction),
+    Aim {
+        x: Literal<'a, i16>,
+        y: Literal<'a, i16>,
+    },
+    Jump,
+}
+
+#[allow(dead_code)]
+#[derive(WriteTypeInfo)]
+struct Position<'a> {
+    x: Literal<'a, i16>,
+    y: Literal<'a, i16>,
+}
+
+#[allow(dead_code)]
+#[derive(WriteTypeInfo)]
+struct Turn<'a> {
+    frame: Literal<'a, u32>,
+    at: Position<'a>,
+}
+
+#[allow(dead_code)]
+#[derive(WriteTypeInfo)]
+struct Empty;
+
+#[allow(dead_code)]
+#[derive(WriteTypeInfo)]
+struct Inbox<'a> {
+    id: Literal<'a, u16>,
+    items: noser::List<'a, Literal<'a, u8>>,
+    note: &'a mut [u8],
+}
+
+#[test]
+fn value_writers() {
+    use noser::traits::{DefaultWriter, WriteTypeInfo};
+    use noser::List;
+
+    let action = ActionWriter::Move(&DirectionWriter::North);
+    assert_eq!(action.create_buffer().unwrap(), [0, 0]);
+    let action = ActionWriter::Move(&DirectionWriter::South);
+    assert_eq!(action.create_buffer().unwrap(), [0, 1]);
+    assert_eq!(ActionWriter::Jump.create_buffer().unwrap(), [2]);
+
+    let aim = ActionWriter::Aim {
+        x: &Literal::<i16>::with_value(-2),
+        y: Literal::<i16>::writer(),
+    };
+    assert_eq!(aim.result_size(), 5);
+    assert_eq!(aim.create_buffer().unwrap(), [1, 254, 255, 0, 0]);
+
+    let origin = PositionWriter {
+        x: &Literal::<i16>::with_value(1),
+        y: &Literal::<i16>::with_value(-1),
+    };
+    let turn = TurnWriter {
+        frame: &Literal::<u32>::with_value(9),
+        at: &origin,
+    };
+    assert_eq!(turn.create_buffer().unwrap(), [9, 0, 0, 0, 1, 0, 255, 255]);
+
+    let later = TurnWriter {
+        frame: &Literal::<u32>::with_value(10),
+        at: Position::writer(),
+    };
+    let turns = List::<Turn>::from(&[&turn, &later])
+        .create_buffer()
+        .unwrap();
+    assert_eq!(
+        turns,
+        [2, 0, 0, 0, 9, 0, 0, 0, 1, 0, 255, 255, 10, 0, 0, 0, 0, 0, 0, 0]
+    );
+
+    let unnamed = UnnamedWriter(&Literal::<u8>::with_value(1));
+    assert_eq!(unnamed.create_buffer().unwrap(), [1]);
+    assert!(EmptyWriter.create_buffer().unwrap().is_empty());
+}
+
+#[test]
+fn dynamic_field_writers() {
+    use noser::traits::WriteTypeInfo;
+    use noser::writer::slice::SliceWriter;
+
+    let inbox = InboxWriter {
+        id: &Literal::<u16>::with_value(4),
+        items: &WithCapacity::with_capacity(2),
+        note: &SliceWriter::with_capacity(1),
+    };
+
+    assert_eq!(
+        inbox.create_buffer().unwrap(),
+        [4, 0, 2, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0]
+    );
+}
@@ -0,0 +1,126 @@
+use noser::json::{from_json, to_json, JsonWriter, Value};
+use noser::{List, Literal};
+use noserc::{Build, DynamicSizeable, Json};
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Json)]
+enum Direction {
+    North,
+    East,
+    West,
+    South,
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Json)]
+enum Action<'a> {
+    Move(Direction),
+    Aim(Literal<'a, f32>, Literal<'a, f32>),
+    Say { text: &'a [u8] },
+    Jump,
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Json)]
+struct Proto<'a> {
+    frame: Literal<'a, u8>,
+    actions: List<'a, Action<'a>>,
+}
+
+#[allow(dead_code)]
+#[derive(Json)]
+struct Pair<'a, T>(Literal<'a, char>, T);
+
+#[allow(dead_code)]
+#[derive(Json)]
+struct Unit;
+
+const PROTO: &str = concat!(
+    r#"{"frame":7,"actions":["#,
+    r#"{"Move":"West"},"#,
+    r#"{"Aim":[0.5,-1.0]},"#,
+    r#"{"Say":{"text":[104,105]}},"#,
+    r#""Jump""#,
+    r#"]}"#
+);
+
+#[test]
+fn round_trip() {
+    let value = Value::parse(PROTO).unwrap();
+    let arena = from_json::<Proto>(&value).unwrap();
+
+    assert_eq!(to_json::<Proto>(&arena).unwrap().to_string(), PROTO);
+}
+
+#[test]
+fn imprinted_buffer_is_readable() {
+    use noser::traits::Build;
+
+    let value = Value::parse(PROTO).unwrap();
+    let mut arena = from_json::<Proto>(&value).unwrap();
+    let proto = Proto::create(&mut arena).unwrap();
+
+    assert_eq!(proto.frame.read(), 7);
+    assert_eq!(proto.actions.capacity(), 4);
+    assert!(freyr::matches!(
+        *proto.actions.borrow(0),
+        Action::Move(Direction::West)
+    ));
+    assert!(freyr::matches!(*proto.actions.borrow(3), Action::Jump));
+}
+
+#[test]
+fn writer_composes_with_lists() {
+    use noser::traits::WriteTypeInfo;
+
+    let first = Value::parse(r#"["a",{"North":null}]"#).unwrap();
+    let second = Value::parse(r#"["b","South"]"#).unwrap();
+
+    let arena = List::from(&[
+        &JsonWriter::<Pair<Direction>>::new(&first).unwrap(),
+        &JsonWriter::<Pair<Direction>>::new(&second).unwrap(),
+    ])
+    .create_buffer()
+    .unwrap();
+
+    assert_eq!(
+        to_json::<List<Pair<Direction>>>(&arena)
+            .unwrap()
+            .to_string(),
+        r#"[["a","North"],["b","South"]]"#
+    );
+}
+
+#[test]
+fn unit_struct() {
+    let arena = from_json::<Unit>(&Value::Null).unwrap();
+
+    assert!(arena.is_empty());
+    assert_eq!(to_json::<Unit>(&arena).unwrap(), Value::Null);
+}
+
+#[test]
+fn shape_errors() {
+    use noser::json::JsonError;
+    use noser::NoserError;
+
+    let cases = [
+        (r#"{"frame":7}"#, JsonError::MissingField("actions")),
+        (r#"{"frame":300,"actions":[]}"#, JsonError::OutOfRange),
+        (
+            r#"{"frame":1,"actions":["Fly"]}"#,
+            JsonError::UnknownVariant("Fly".to_string()),
+        ),
+        (
+            r#"{"frame":1,"actions":[{"Aim":[1]}]}"#,
+            JsonError::Mismatch("array of matching length"),
+        ),
+    ];
+
+    for (text, expected) in cases.iter() {
+        match from_json::<Proto>(&Value::parse(text).unwrap()) {
+            Err(NoserError::Json(e)) => assert_eq!(&e, expected),
+            other => panic!("{} gave {:?}", text, other),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use noser::json::{from_json, to_json, Value};
+use noser::traits::{Build, Floats, Read, Structural, Write};
+use noser::{List, Literal};
+use noserc::{Build, DynamicSizeable, Json, JsonLiteral, LiteralInnerType, Structural};
+
+#[derive(Clone, Copy, Debug, PartialEq, LiteralInnerType, JsonLiteral)]
+struct PlayerId(u64);
+
+#[derive(Clone, Copy, Debug, PartialEq, LiteralInnerType, JsonLiteral)]
+struct Health {
+    points: i16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, LiteralInnerType, JsonLiteral)]
+#[noserc(tag_bytes = 1)]
+enum Direction {
+    North,
+    East,
+    #[noserc(tag = 7)]
+    West,
+    South,
+}
+
+/// The same variants deriving `Build`, which literal enums are laid out like.
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Json)]
+#[noserc(tag_bytes = 1)]
+enum Heading {
+    North,
+    East,
+    #[noserc(tag = 7)]
+    West,
+    South,
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Json, Structural)]
+struct Player<'a> {
+    id: Literal<'a, PlayerId>,
+    health: Literal<'a, Health>,
+    facing: Literal<'a, Direction>,
+    path: List<'a, Literal<'a, Direction>>,
+}
+
+const PLAYER: &str = r#"{"id":42,"health":-3,"facing":"West","path":["North","South"]}"#;
+
+#[test]
+fn derived_literals() {
+    let mut arena = from_json::<Player>(&Value::parse(PLAYER).unwrap()).unwrap();
+    assert_eq!(arena.len(), 8 + 2 + 1 + 4 + 2);
+    assert_eq!(to_json::<Player>(&arena).unwrap().to_string(), PLAYER);
+
+    let player = Player::create(&mut arena).unwrap();
+    assert_eq!(player.id.read(), PlayerId(42));
+    assert_eq!(player.health.read(), Health { points: -3 });
+    assert_eq!(player.facing.read(), Ok(Direction::West));
+    assert_eq!(player.path.borrow(1).read(), Ok(Direction::South));
+}
+
+#[test]
+fn enums_match_build_layout() {
+    for name in &["North", "East", "West", "South"] {
+        let json = Value::String(name.to_string());
+
+        assert_eq!(
+            from_json::<Literal<Direction>>(&json).unwrap(),
+            from_json::<Heading>(&json).unwrap()
+        );
+    }
+}
+
+#[test]
+fn invalid_discriminants() {
+    for byte in 0..=255u8 {
+        let expected = match byte {
+            0 => Ok(Direction::North),
+            1 => Ok(Direction::East),
+            7 => Ok(Direction::West),
+            8 => Ok(Direction::South),
+            tag => Err(u64::from(tag)),
+        };
+
+        assert_eq!(Direction::read(&[byte]), expected);
+    }
+
+    let mut arena = vec![0; 1];
+    Direction::write(&mut arena, Direction::South);
+    assert_eq!(arena, [8]);
+
+    arena[0] = 3;
+    assert_eq!(to_json::<Literal<Direction>>(&arena).unwrap(), Value::Null);
+    assert!(from_json::<Literal<Direction>>(&Value::parse(r#""Up""#).unwrap()).is_err());
+}
+
+#[test]
+fn structural_order() {
+    let parse = |json: &str| from_json::<Player>(&Value::parse(json).unwrap()).unwrap();
+
+    let a = parse(PLAYER);
+    let b = parse(&PLAYER.replace("South", "East"));
+    let (ordering, _, _) = Player::compare(&a, &b, Floats::Total).unwrap();
+    assert_eq!(ordering, Some(std::cmp::Ordering::Greater));
+
+    // Unknown tags order by their value, like they hash.
+    let (ordering, _, _) = Literal::<Direction>::compare(&[3], &[7], Floats::Total).unwrap();
+    assert_eq!(ordering, Some(std::cmp::Ordering::Less));
+}
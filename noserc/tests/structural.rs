@@ -0,0 +1,129 @@
+use noser::traits::{Floats, Structural};
+use noser::{DynamicEnumWrapper, List, Literal};
+use noserc::{Build, DynamicEnum, DynamicSizeable, Structural};
+use std::cmp::Ordering;
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, DynamicEnum, Structural)]
+enum Slot<'a> {
+    Empty,
+    Full(Literal<'a, u32>),
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Structural)]
+struct Reading<'a> {
+    id: Literal<'a, u8>,
+    value: Literal<'a, f32>,
+    slot: DynamicEnumWrapper<'a, Slot<'a>>,
+    tags: List<'a, Literal<'a, u8>>,
+}
+
+fn reading(id: u8, value: f32, footprint: u32, slot: Option<u32>, tags: &[u8]) -> Vec<u8> {
+    let mut arena = vec![id];
+    arena.extend(&value.to_le_bytes());
+
+    arena.extend(&footprint.to_le_bytes());
+    let start = arena.len();
+    match slot {
+        Some(full) => {
+            arena.push(1);
+            arena.extend(&full.to_le_bytes());
+        }
+        None => arena.push(0),
+    }
+    arena.resize(start + footprint as usize, 0);
+
+    arena.extend(&(tags.len() as u32).to_le_bytes());
+    arena.extend(tags);
+    arena
+}
+
+fn compare(a: &[u8], b: &[u8], floats: Floats) -> Option<Ordering> {
+    Reading::compare(a, b, floats).unwrap().0
+}
+
+#[test]
+fn equal_across_arenas() {
+    let mut a = reading(1, 2.5, 1, None, &[1, 2]);
+    let mut b = reading(1, 2.5, 16, None, &[1, 2]);
+    assert_ne!(a.len(), b.len());
+
+    a.push(3);
+    b.extend(&[4, 5]);
+    assert_eq!(
+        Reading::compare(&a, &b, Floats::Ieee).unwrap(),
+        (Some(Ordering::Equal), &[3][..], &[4, 5][..])
+    );
+    assert_eq!(
+        Reading::content_hash(&a).unwrap(),
+        Reading::content_hash(&b).unwrap()
+    );
+
+    let c = reading(1, 2.5, 5, Some(0), &[1, 2]);
+    assert!(!Reading::equal(&a, &c, Floats::Ieee).unwrap());
+    assert_ne!(
+        Reading::content_hash(&a).unwrap(),
+        Reading::content_hash(&c).unwrap()
+    );
+}
+
+#[test]
+fn floats() {
+    let nan = reading(1, f32::NAN, 1, None, &[]);
+    assert_eq!(compare(&nan, &nan, Floats::Ieee), None);
+    assert_eq!(compare(&nan, &nan, Floats::Total), Some(Ordering::Equal));
+
+    let negative = reading(1, -0.0, 1, None, &[]);
+    let positive = reading(1, 0.0, 1, None, &[]);
+    assert_eq!(
+        compare(&negative, &positive, Floats::Ieee),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        compare(&negative, &positive, Floats::Total),
+        Some(Ordering::Less)
+    );
+}
+
+#[test]
+fn ordering() {
+    let base = reading(2, 1.0, 5, Some(7), &[1, 2]);
+    let cases = vec![
+        (reading(1, 9.0, 5, Some(9), &[9]), Ordering::Less),
+        (reading(2, 0.5, 5, Some(9), &[9]), Ordering::Less),
+        (reading(2, 1.0, 1, None, &[9]), Ordering::Less),
+        (reading(2, 1.0, 5, Some(6), &[9]), Ordering::Less),
+        (reading(2, 1.0, 5, Some(8), &[]), Ordering::Greater),
+        (reading(2, 1.0, 5, Some(7), &[1]), Ordering::Less),
+        (reading(2, 1.0, 5, Some(7), &[1, 2, 0]), Ordering::Greater),
+        (reading(2, 1.0, 5, Some(7), &[2]), Ordering::Greater),
+    ];
+
+    for (other, ordering) in cases {
+        assert_eq!(compare(&other, &base, Floats::Total), Some(ordering));
+        assert_eq!(
+            compare(&base, &other, Floats::Total),
+            Some(ordering.reverse())
+        );
+    }
+}
+
+#[test]
+fn malformed() {
+    let mut unknown = reading(1, 1.0, 1, None, &[]);
+    unknown[9] = 2;
+    assert!(Reading::equal(&unknown, &unknown, Floats::Total).is_err());
+    assert!(Reading::content_hash(&unknown).is_err());
+
+    let truncated = reading(1, 1.0, 1, None, &[]);
+    assert!(Reading::content_hash(&truncated[..12]).is_err());
+}
+
+#[test]
+fn stable_hash() {
+    assert_eq!(
+        Literal::<u32>::content_hash(&[1, 0, 0, 0]).unwrap(),
+        0xad2a_ca77_4798_5764
+    );
+}
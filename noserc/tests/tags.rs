@@ -0,0 +1,84 @@
+use noser::json::{from_json, to_json, Value};
+use noser::traits::{size::Sizeable, Build, Tagged, WriteTypeInfo};
+use noser::Literal;
+use noserc::{Build, DynamicSizeable, Json, StaticEnum, WriteTypeInfo};
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, WriteTypeInfo, Json)]
+#[noserc(tag_bytes = 2)]
+enum Message<'a> {
+    #[noserc(tag = 10)]
+    Ping,
+    Data(Literal<'a, u8>),
+    #[noserc(tag = 3)]
+    Close,
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Json)]
+enum Single {
+    Only,
+}
+
+#[allow(dead_code)]
+#[derive(Build, DynamicSizeable, Json)]
+#[noserc(tag_bytes = 1)]
+enum Padded {
+    Only,
+}
+
+#[allow(dead_code)]
+#[derive(StaticEnum)]
+enum Wide<'a> {
+    #[noserc(tag = 300)]
+    A(Literal<'a, u8>),
+    B,
+}
+
+fn bytes<T: noser::traits::Json>(json: &str) -> Vec<u8> {
+    from_json::<T>(&Value::parse(json).unwrap()).unwrap()
+}
+
+#[test]
+fn explicit_tags() {
+    assert_eq!(bytes::<Message>(r#""Ping""#), [10, 0]);
+    assert_eq!(bytes::<Message>(r#"{"Data":7}"#), [11, 0, 7]);
+    assert_eq!(bytes::<Message>(r#""Close""#), [3, 0]);
+
+    let mut arena = vec![11, 0, 7];
+    assert_eq!(Message::read_size(&arena).unwrap(), 3);
+    assert_eq!(
+        to_json::<Message>(&arena).unwrap().to_string(),
+        r#"{"Data":7}"#
+    );
+
+    match Message::create(&mut arena).unwrap() {
+        Message::Data(data) => assert_eq!(data.read(), 7),
+        _ => panic!("expected Message::Data"),
+    }
+
+    let mut unknown = vec![0, 0];
+    assert!(Message::create(&mut unknown).is_err());
+    assert!(Message::read_size(&unknown).is_err());
+}
+
+#[test]
+fn writer_uses_tags() {
+    assert_eq!(ImprintMessage::Ping.create_buffer().unwrap(), [10, 0]);
+    assert_eq!(ImprintMessage::Close.create_buffer().unwrap(), [3, 0]);
+    assert_eq!(ImprintMessage::Data.create_buffer().unwrap(), [11, 0, 0]);
+}
+
+#[test]
+fn tag_width() {
+    assert!(bytes::<Single>(r#""Only""#).is_empty());
+    assert_eq!(bytes::<Padded>(r#""Only""#), [0]);
+
+    assert_eq!(<Wide as noser::traits::StaticEnum>::VARIANT_BYTES, 2);
+    assert_eq!(WideVariant::B.variant_tag(), 301);
+    assert!(freyr::matches!(
+        WideVariant::from_tag(300),
+        Ok(WideVariant::A)
+    ));
+    assert!(WideVariant::from_tag(0).is_err());
+}
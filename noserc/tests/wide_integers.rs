@@ -0,0 +1,27 @@
+use noser::json::{from_json, to_json, Value};
+use noser::traits::{Build, Floats, Structural};
+use noser::Literal;
+use noserc::{Build, Json, StaticSizeable, Structural};
+
+#[allow(dead_code)]
+#[derive(Build, StaticSizeable, Json, Structural)]
+struct Ledger<'a> {
+    balance: Literal<'a, i128>,
+    total: Literal<'a, u128>,
+}
+
+#[test]
+fn wide_integers() {
+    let text = r#"{"balance":"-170141183460469231731687303715884105728","total":5}"#;
+    let mut arena = from_json::<Ledger>(&Value::parse(text).unwrap()).unwrap();
+    assert_eq!(arena.len(), 32);
+    assert_eq!(to_json::<Ledger>(&arena).unwrap().to_string(), text);
+
+    let ledger = Ledger::create(&mut arena).unwrap();
+    assert_eq!(ledger.balance.read(), i128::MIN);
+    assert_eq!(ledger.total.read(), 5);
+
+    let richer = from_json::<Ledger>(&Value::parse(r#"{"balance":1,"total":0}"#).unwrap()).unwrap();
+    let (ordering, _, _) = Ledger::compare(&arena, &richer, Floats::Total).unwrap();
+    assert_eq!(ordering, Some(std::cmp::Ordering::Less));
+}
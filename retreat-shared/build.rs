@@ -0,0 +1,6 @@
+extern crate noser_schema;
+
+fn main() {
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    noser_schema::compile("protocol.noser", format!("{}/protocol.rs", out_dir)).unwrap();
+}
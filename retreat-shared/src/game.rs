@@ -1,27 +1,8 @@
-pub mod net {
-    use noser::{List, Literal};
-    use noserc::{Build, SizableDynamic};
-
-    #[derive(Build, SizableDynamic)]
-    pub enum Direction {
-        North,
-        East,
-        West,
-        South,
-    }
+extern crate noser;
+extern crate noserc;
 
-    #[derive(Build, SizableDynamic)]
-    pub enum Action {
-        Move(Direction),
-        Shoot(Direction),
-        Jump,
-    }
-
-    #[derive(Build, SizableDynamic)]
-    pub struct Proto<'a> {
-        frame: Literal<'a, u8>,
-        actions: List<'a, Action>,
-    }
+pub mod net {
+    include!(concat!(env!("OUT_DIR"), "/protocol.rs"));
 }
 
 pub mod game {